@@ -0,0 +1,48 @@
+//! Renders every page of the site into the dist folder.
+//!
+//! Run from the repository root after the dist folder is prepared: `$ cargo run --bin prerender`
+
+use opensource_website_seed_basic::prerender::{self, PageData};
+use std::{error::Error, fs, path::Path, process};
+
+const DATA_FILE: &str = "public/data.json";
+const CONTENT_FILE: &str = "public/content.json";
+const TEMPLATE_FILE: &str = "index.html";
+const DIST_DIR: &str = "dist";
+/// Served by Netlify for URLs without a rendered page, so the app renders them itself.
+const FALLBACK_FILE: &str = "404.html";
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("prerendering failed: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let page_data = PageData {
+        data: serde_json::from_str(&fs::read_to_string(DATA_FILE)?)?,
+        content: Some(serde_json::from_str(&fs::read_to_string(CONTENT_FILE)?)?),
+    };
+    let template = fs::read_to_string(TEMPLATE_FILE)?;
+    if !template.contains(prerender::APP_ELEMENT) {
+        return Err(format!(
+            "{} doesn't contain {}",
+            TEMPLATE_FILE,
+            prerender::APP_ELEMENT
+        )
+        .into());
+    }
+
+    let dist_dir = Path::new(DIST_DIR);
+    for page in prerender::render(page_data, &template)? {
+        let path = dist_dir.join(&page.file);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, page.html)?;
+        println!("{}", path.display());
+    }
+    fs::write(dist_dir.join(FALLBACK_FILE), template)?;
+    Ok(())
+}
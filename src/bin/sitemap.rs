@@ -0,0 +1,45 @@
+//! Writes `sitemap.xml` and `robots.txt` into the dist folder.
+//!
+//! Run from the repository root after the dist folder is prepared: `$ cargo run --bin sitemap`
+//! The site URL is `site.url` from the data file, or the `URL` environment variable if set.
+
+use opensource_website_seed_basic::{sitemap, Data};
+use std::{env, error::Error, fs, path::Path, process};
+
+const DATA_FILE: &str = "public/data.json";
+const DIST_DIR: &str = "dist";
+/// Overrides `site.url`.
+const SITE_URL_VAR: &str = "URL";
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("generating the sitemap failed: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let data: Data = serde_json::from_str(&fs::read_to_string(DATA_FILE)?)?;
+    // Netlify sets `URL` to the site's main URL during builds.
+    let site_url = env::var(SITE_URL_VAR).unwrap_or_else(|_| data.site_url().to_owned());
+    let invalid_url = || {
+        format!(
+            "{} or site.url in {} has to be an absolute URL, not '{}'",
+            SITE_URL_VAR, DATA_FILE, site_url
+        )
+    };
+    let sitemap = sitemap::sitemap(&data, &site_url).ok_or_else(invalid_url)?;
+    let robots = sitemap::robots(&data, &site_url).ok_or_else(invalid_url)?;
+
+    let dist_dir = Path::new(DIST_DIR);
+    fs::create_dir_all(dist_dir)?;
+    for (file, content) in &[
+        (sitemap::SITEMAP_FILE, sitemap),
+        (sitemap::ROBOTS_FILE, robots),
+    ] {
+        let path = dist_dir.join(file);
+        fs::write(&path, content)?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use seed::{prelude::*, *};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Storage key of the cached `Data`.
+pub const DATA_KEY: &str = "opensource-website-data";
+/// Storage key of the cached `Content`.
+pub const CONTENT_KEY: &str = "opensource-website-content";
+/// Increment when `Data` or `Content` change in a way that old cached entries can't be
+/// deserialized.
+const SCHEMA_VERSION: u32 = 3;
+/// Entries older than 30 days are ignored; revalidation would replace them anyway.
+const MAX_AGE_MS: f64 = 30. * 24. * 60. * 60. * 1000.;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry<D> {
+    schema_version: u32,
+    /// Milliseconds since the Unix epoch.
+    timestamp: f64,
+    pub etag: Option<String>,
+    pub data: D,
+}
+
+/// Returns the last successfully fetched data stored under `key`, if they're still usable.
+pub fn load<D: DeserializeOwned>(key: &str) -> Option<CacheEntry<D>> {
+    let entry: CacheEntry<D> = match LocalStorage::get(key) {
+        Ok(entry) => entry,
+        // Missing, or not deserializable with the current `D` despite the schema version.
+        Err(_) => {
+            LocalStorage::remove(key).ok();
+            return None;
+        }
+    };
+    let expired = js_sys::Date::now() - entry.timestamp > MAX_AGE_MS;
+    if entry.schema_version != SCHEMA_VERSION || expired {
+        LocalStorage::remove(key).ok();
+        return None;
+    }
+    Some(entry)
+}
+
+pub fn store<D: Serialize>(key: &str, data: &D, etag: Option<String>) {
+    let entry = CacheEntry {
+        schema_version: SCHEMA_VERSION,
+        timestamp: js_sys::Date::now(),
+        etag,
+        data,
+    };
+    if let Err(error) = LocalStorage::insert(key, &entry) {
+        error!("cannot cache data:", error);
+    }
+}
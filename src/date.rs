@@ -0,0 +1,153 @@
+//! Calendar dates used in `data.json`.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A date written as `YYYY-MM-DD`; dates are ordered chronologically.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub const fn year(self) -> u16 {
+        self.year
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let error = || format!("invalid date '{}', expected YYYY-MM-DD", value);
+
+        let mut parts = value.splitn(3, '-');
+        let mut next_part = |len| {
+            parts
+                .next()
+                .filter(|part: &&str| part.len() == len && part.bytes().all(|b| b.is_ascii_digit()))
+        };
+        let (year, month, day) = match (next_part(4), next_part(2), next_part(2)) {
+            (Some(year), Some(month), Some(day)) => (year, month, day),
+            _ => return Err(error()),
+        };
+        let date = Self {
+            year: year.parse().map_err(|_| error())?,
+            month: month.parse().map_err(|_| error())?,
+            day: day.parse().map_err(|_| error())?,
+        };
+        if !(1..=12).contains(&date.month)
+            || date.day == 0
+            || date.day > days_in_month(date.year, date.month)
+        {
+            return Err(error());
+        }
+        Ok(date)
+    }
+}
+
+/// Deserializes an optional date and reads a malformed one as `None`, so a single bad date
+/// doesn't prevent the whole data file from loading; validation reports it as missing.
+pub fn deserialize_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Date>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| Date::try_from(value).ok()))
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<Date, String> {
+        Date::try_from(value.to_owned())
+    }
+
+    #[test]
+    fn valid_dates() {
+        let date = parse("2019-06-12").expect("valid date");
+        assert_eq!(date.year(), 2019);
+        assert_eq!(date.to_string(), "2019-06-12");
+        assert!(parse("2019-12-31") < parse("2020-01-01"));
+    }
+
+    #[test]
+    fn invalid_months_and_days() {
+        for value in &[
+            "2019-00-12",
+            "2019-13-12",
+            "2019-06-00",
+            "2019-06-31",
+            "2019-01-32",
+        ] {
+            assert_eq!(
+                parse(value),
+                Err(format!("invalid date '{}', expected YYYY-MM-DD", value))
+            );
+        }
+    }
+
+    #[test]
+    fn february_29() {
+        assert!(parse("2020-02-29").is_ok());
+        assert!(parse("2000-02-29").is_ok());
+        assert!(parse("2019-02-29").is_err());
+        assert!(parse("1900-02-29").is_err());
+        assert!(parse("2020-02-30").is_err());
+    }
+
+    #[test]
+    fn non_iso_dates() {
+        for value in &[
+            "12/06/2019",
+            "2019-6-12",
+            "19-06-12",
+            "2019-06-12T10:00:00",
+            "2019-06",
+            "+201-06-12",
+            "",
+        ] {
+            assert!(parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn lenient_deserialization() {
+        let deserialize = |value| deserialize_lenient(value).expect("a string or null");
+        assert_eq!(
+            deserialize(serde_json::json!("2019-06-12")),
+            parse("2019-06-12").ok()
+        );
+        assert_eq!(deserialize(serde_json::json!("12/06/2019")), None);
+        assert_eq!(deserialize(serde_json::Value::Null), None);
+    }
+}
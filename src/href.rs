@@ -0,0 +1,99 @@
+//! Formatting of `Url`s for links.
+//!
+//! Seed formats URLs with browser APIs, so views format their links by `Href::href` to be
+//! renderable outside of the browser, too (see `prerender`).
+
+use seed::prelude::*;
+use std::fmt::Write;
+
+/// Characters `encodeURIComponent` leaves unescaped besides ASCII alphanumerics.
+const PATH_UNRESERVED: &str = "-_.!~*'()";
+/// Characters `URLSearchParams` leaves unescaped besides ASCII alphanumerics.
+const SEARCH_UNRESERVED: &str = "-_.*";
+
+pub trait Href {
+    /// The same string as the browser would produce for the URL.
+    fn href(&self) -> String;
+}
+
+impl Href for Url {
+    fn href(&self) -> String {
+        let path = self
+            .path()
+            .iter()
+            .map(|part| encode(part, PATH_UNRESERVED))
+            .collect::<Vec<_>>();
+        let search = self
+            .search()
+            .iter()
+            .flat_map(|(key, values)| {
+                values
+                    .iter()
+                    .map(move |value| format!("{}={}", encode_search(key), encode_search(value)))
+            })
+            .collect::<Vec<_>>();
+
+        let mut href = format!("/{}", path.join("/"));
+        if !search.is_empty() {
+            href.push('?');
+            href.push_str(&search.join("&"));
+        }
+        if let Some(hash) = self.hash() {
+            href.push('#');
+            href.push_str(hash);
+        }
+        href
+    }
+}
+
+/// Form encoding of search parameters, spaces become `+`.
+fn encode_search(text: &str) -> String {
+    text.split(' ')
+        .map(|part| encode(part, SEARCH_UNRESERVED))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Percent-encodes UTF-8 bytes of `text` except ASCII alphanumerics and `unreserved`.
+fn encode(text: &str, unreserved: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || unreserved.contains(c) {
+            encoded.push(c);
+        } else {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                write!(encoded, "%{:02X}", byte).ok();
+            }
+        }
+    }
+    encoded
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let url = Url::new().set_path(["projects", "a b/c", "é(1)"]);
+        assert_eq!(url.href(), "/projects/a%20b%2Fc/%C3%A9(1)");
+    }
+
+    #[test]
+    fn search_and_hash() {
+        let mut url = Url::new().set_path(["search"]).set_hash("top");
+        url.search_mut().push_value("q", "c++ gamedev".to_owned());
+        url.search_mut().push_value("tag", "ü".to_owned());
+        assert_eq!(url.href(), "/search?q=c%2B%2B+gamedev&tag=%C3%BC#top");
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Url::new().href(), "/");
+    }
+}
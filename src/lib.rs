@@ -4,45 +4,172 @@
     clippy::must_use_candidate
 )]
 
+use href::Href;
 use seed::{prelude::*, *};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::mem;
 
+mod cache;
+mod date;
+mod href;
+mod markdown;
 mod page;
+pub mod prerender;
+mod query;
+mod recent_searches;
+mod sanitize;
+mod search;
+pub mod sitemap;
+mod suggest;
+#[cfg(test)]
+mod test_data;
+mod validation;
 
 // -- Url parts --
 const TAGS: &str = "tags";
 const TAGS_TAG_PARAMETER: &str = "tag";
+const TAGS_MATCH_PARAMETER: &str = "match";
+const SORT_PARAMETER: &str = "sort";
+pub const PROJECT_LIST_FRAGMENT: &str = "project-list";
+const PROJECTS: &str = "projects";
+const SEARCH: &str = "search";
+const NEWSLETTER: &str = "newsletter";
+const SPONSORSHIPS: &str = "sponsorships";
+const SPONSORSHIPS_PLATFORM_PARAMETER: &str = "platform";
+const SEARCH_QUERY_PARAMETER: &str = "q";
 
 type Tag = String;
 
+/// Elements inside the search overlay reachable by Tab.
+const SEARCH_OVERLAY_FOCUSABLE: &str = ".search-overlay input, .search-overlay a[href]";
+
+/// Tags offered in the search overlay when there's nothing else to show.
+const POPULAR_TAG_COUNT: usize = 8;
+/// Search results are recomputed once typing pauses for this long.
+const SEARCH_DEBOUNCE_MS: u32 = 150;
+
+const DATA_URL: &str = "/public/data.json";
+const CONTENT_URL: &str = "/public/content.json";
+const RETRY_BASE_DELAY_MS: u32 = 500;
+const RETRY_MAX_DELAY_MS: u32 = 30_000;
+
 // ------ ------
 //     Init
 // ------ ------
 
 fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.subscribe(Msg::UrlChanged).perform_cmd(async {
-        Msg::DataFetched(
-            async {
-                fetch("/public/data.json")
-                    .await?
-                    .check_status()?
-                    .json()
-                    .await
-            }
-            .await,
-        )
+    orders
+        .subscribe(Msg::UrlChanged)
+        .subscribe(Msg::UrlRequested)
+        .stream(streams::window_event(Ev::KeyDown, |event| {
+            let event = event.unchecked_into::<web_sys::KeyboardEvent>();
+            IF!(is_search_shortcut(&event) => {
+                event.prevent_default();
+                Msg::OpenSearch
+            })
+        }));
+
+    // Scroll positions are restored by `Msg::UrlChanged` once the page is rendered.
+    if let Ok(history) = window().history() {
+        history
+            .set_scroll_restoration(web_sys::ScrollRestoration::Manual)
+            .ok();
+    }
+    if let Some(fragment) = url.hash().cloned() {
+        orders.after_next_render(move |_| scroll_page(None, Some(&fragment)));
+    }
+
+    let mut model = Model::new(url, recent_searches::load());
+    if let Page::Search(query) = &model.page {
+        model.search_query = query.clone();
+    }
+
+    // Render the prerendered or cached data immediately and revalidate them in the background.
+    let (prerendered_data, prerendered_content) = prerender::load_page_data()
+        .map_or((None, None), |page_data| {
+            (Some(page_data.data), page_data.content)
+        });
+    if let Some(data) = prerendered_data {
+        set_data(&mut model, data);
+        model.data_status = DataStatus::Loaded;
+    } else if let Some(cache_entry) = cache::load(cache::DATA_KEY) {
+        set_data(&mut model, cache_entry.data);
+        model.data_etag = cache_entry.etag;
+        model.data_status = DataStatus::Loaded;
+    }
+    let cached_content = || cache::load(cache::CONTENT_KEY).map(|cache_entry| cache_entry.data);
+    if let Some(content) = prerendered_content.or_else(cached_content) {
+        model.content = Some(content);
+        model.content_status = DataStatus::Loaded;
+    }
+    model.data_request = Some(fetch_data(orders, 0, model.data_etag.clone()));
+    model.content_request = Some(fetch_content(orders, 0));
+
+    model
+}
+
+/// `/` outside of text fields, or Ctrl+K (Cmd+K on macOS).
+fn is_search_shortcut(event: &web_sys::KeyboardEvent) -> bool {
+    let typing = document().active_element().map_or(false, |element| {
+        matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA")
     });
+    let modifier = event.ctrl_key() || event.meta_key();
+    (modifier && event.key().eq_ignore_ascii_case("k"))
+        || (!typing && !modifier && event.key() == "/")
+}
 
-    Model {
-        base_url: url.to_base_url(),
-        data: Data {
-            projects: Vec::new(),
-        },
-        show_search: false,
-        search_query: String::new(),
-        search_input_element: ElRef::default(),
-        page: Page::init(url),
+/// Dropping the returned handle aborts the request, see `Model::data_request`.
+fn fetch_data(orders: &mut impl Orders<Msg>, delay_ms: u32, etag: Option<String>) -> CmdHandle {
+    orders.perform_cmd_with_handle(async move {
+        if delay_ms > 0 {
+            cmds::timeout(delay_ms, || ()).await;
+        }
+        Msg::DataFetched(request_data(etag).await)
+    })
+}
+
+async fn request_data(etag: Option<String>) -> fetch::Result<FetchedData> {
+    let mut request = Request::new(DATA_URL);
+    if let Some(etag) = etag {
+        request = request.header(Header::custom("If-None-Match", etag));
     }
+    let response = request.fetch().await?;
+
+    if response.status().code == 304 {
+        return Ok(FetchedData::NotModified);
+    }
+    let response = response.check_status()?;
+    let etag = response.raw_response().headers().get("ETag").ok().flatten();
+    Ok(FetchedData::Modified {
+        data: response.json().await?,
+        etag,
+    })
+}
+
+/// Like `fetch_data`, see `Model::content_request`.
+fn fetch_content(orders: &mut impl Orders<Msg>, delay_ms: u32) -> CmdHandle {
+    orders.perform_cmd_with_handle(async move {
+        if delay_ms > 0 {
+            cmds::timeout(delay_ms, || ()).await;
+        }
+        Msg::ContentFetched(request_content().await)
+    })
+}
+
+async fn request_content() -> fetch::Result<Content> {
+    Request::new(CONTENT_URL)
+        .fetch()
+        .await?
+        .check_status()?
+        .json()
+        .await
+}
+
+pub enum FetchedData {
+    NotModified,
+    Modified { data: Data, etag: Option<String> },
 }
 
 // ------ ------
@@ -51,20 +178,300 @@ fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
 
 pub struct Model {
     base_url: Url,
+    /// Key of the current page in `scroll_positions`.
+    current_url: String,
+    /// Vertical scroll offsets of visited pages, restored on history back/forward.
+    scroll_positions: HashMap<String, f64>,
+    /// Set when a link is clicked, so the next `UrlChanged` isn't a history back/forward.
+    forward_navigation: bool,
     data: Data,
+    data_etag: Option<String>,
+    /// Home page texts; their sections stay empty until they are loaded.
+    content: Option<Content>,
+    content_status: DataStatus,
+    content_request: Option<CmdHandle>,
     show_search: bool,
     search_query: String,
+    /// Index of the search overlay result opened by Enter.
+    selected_search_result: usize,
+    /// Rebuilt whenever `data` changes.
+    search_index: search::SearchIndex,
+    /// Results for `search_query`, shared by the overlay and the search page.
+    search_results: Vec<search::SearchResult>,
+    /// The pending recomputation of `search_results`; replacing it restarts the debounce.
+    search_timeout: Option<CmdHandle>,
+    /// Persisted queries offered while the search overlay input is empty.
+    recent_searches: Vec<String>,
     search_input_element: ElRef<web_sys::HtmlInputElement>,
     page: Page,
+    data_status: DataStatus,
+    /// The pending data request; replacing it aborts a scheduled retry.
+    data_request: Option<CmdHandle>,
+    data_issues: Vec<validation::Issue>,
+}
+
+impl Model {
+    /// The model of the page at `url`, its data are loaded afterwards.
+    fn new(url: Url, recent_searches: Vec<String>) -> Self {
+        Self {
+            base_url: url.to_base_url(),
+            current_url: url.href(),
+            scroll_positions: HashMap::new(),
+            forward_navigation: false,
+            data: Data::default(),
+            data_etag: None,
+            data_request: None,
+            content: None,
+            content_status: DataStatus::Loading { attempt: 0 },
+            content_request: None,
+            show_search: false,
+            search_query: String::new(),
+            selected_search_result: 0,
+            search_index: search::SearchIndex::default(),
+            search_results: Vec::new(),
+            search_timeout: None,
+            recent_searches,
+            search_input_element: ElRef::default(),
+            page: Page::init(url),
+            data_status: DataStatus::Loading { attempt: 0 },
+            data_issues: Vec::new(),
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Data {
+    site: SiteConfig,
+    #[serde(default)]
+    tags: BTreeMap<Tag, TagInfo>,
+    /// Sections of the home page between the featured projects and the sponsorship.
+    #[serde(default)]
+    home_sections: Vec<HomeSection>,
+    #[serde(default)]
+    newsletter_editions: Vec<NewsletterEdition>,
+    #[serde(default)]
+    sponsorships: Vec<Sponsorship>,
     projects: Vec<Project>,
 }
 
-#[derive(Deserialize, Debug)]
+impl Data {
+    /// Public URL of this website, see `SiteConfig::url`.
+    pub fn site_url(&self) -> &str {
+        &self.site.url
+    }
+
+    pub fn tag_info(&self, tag: &str) -> Option<&TagInfo> {
+        self.tags.get(tag)
+    }
+
+    /// Undeclared tags are labeled by their names.
+    pub fn tag_label<'a>(&'a self, tag: &'a str) -> &'a str {
+        self.tag_info(tag).map_or(tag, |tag_info| &tag_info.label)
+    }
+
+    /// Newsletter editions, the latest first; editions without a date last.
+    pub fn newsletter_editions(&self) -> Vec<&NewsletterEdition> {
+        let mut editions = self.newsletter_editions.iter().collect::<Vec<_>>();
+        editions.sort_by_key(|edition| Reverse(edition.date));
+        editions
+    }
+
+    /// Sponsorships on `platform` (or all of them) grouped by platform, the longest running first
+    /// and those without a start date last.
+    pub fn sponsorships_by_platform(
+        &self,
+        platform: Option<SponsorshipPlatform>,
+    ) -> BTreeMap<SponsorshipPlatform, Vec<&Sponsorship>> {
+        let mut sponsorships = BTreeMap::<_, Vec<_>>::new();
+        for sponsorship in &self.sponsorships {
+            if platform.map_or(true, |platform| platform == sponsorship.platform) {
+                sponsorships
+                    .entry(sponsorship.platform)
+                    .or_default()
+                    .push(sponsorship);
+            }
+        }
+        for platform_sponsorships in sponsorships.values_mut() {
+            platform_sponsorships.sort_by_key(|sponsorship| {
+                (sponsorship.start_date.is_none(), sponsorship.start_date)
+            });
+        }
+        sponsorships
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteConfig {
+    title: String,
+    org_name: String,
+    forge_url: String,
+    homepage: String,
+    /// Public URL of this website, e.g. `https://embark.dev`; sitemaps need absolute URLs.
+    /// The `URL` environment variable overrides it when the sitemap is generated.
+    #[serde(default)]
+    url: String,
+    logo: String,
+    social_links: Vec<SocialLink>,
+}
+
+impl SiteConfig {
+    /// Star buttons are provided by ghbtns.com, so they only work for GitHub organizations.
+    pub fn is_github(&self) -> bool {
+        self.forge_url.trim_end_matches('/') == "https://github.com"
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SocialLink {
+    /// Font Awesome icon name without the `fa-` prefix, e.g. `twitter`.
+    icon: String,
+    url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagInfo {
+    label: String,
+    /// Hex color, e.g. `#731b00`.
+    color: Option<String>,
+    description: Option<String>,
+    /// Font Awesome icon name without the `fa-` prefix, e.g. `cloud`.
+    icon: Option<String>,
+}
+
+/// A home page section listing the projects with any of its tags.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeSection {
+    /// Element id, so the section can be linked to, e.g. `/#rust`.
+    id: Option<String>,
+    title: Option<String>,
+    /// Image URL displayed before the title, e.g. a white SVG logo.
+    logo: Option<String>,
+    /// Markdown.
+    intro: Option<String>,
+    tags: Vec<Tag>,
+    /// Sections with a background span the full width of the page.
+    background: Option<Background>,
+    cta: Option<CallToAction>,
+    #[serde(default)]
+    layout: SectionLayout,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Background {
+    Blue,
+    Red,
+    Black,
+    Grey,
+}
+
+impl Background {
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Blue => "background-blue",
+            Self::Red => "background-red",
+            Self::Black => "background-black",
+            Self::Grey => "background-grey",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToAction {
+    label: String,
+    url: String,
+    background: Option<Background>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SectionLayout {
+    /// Project cards followed by the call to action.
+    Cards,
+    /// The call to action followed by an "Our ... projects" category for every tag.
+    Categories,
+}
+
+impl Default for SectionLayout {
+    fn default() -> Self {
+        Self::Categories
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsletterEdition {
+    #[serde(default, deserialize_with = "date::deserialize_lenient")]
+    date: Option<date::Date>,
+    title: String,
+    url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sponsorship {
+    recipient: String,
+    platform: SponsorshipPlatform,
+    url: String,
+    avatar: Option<String>,
+    #[serde(default, deserialize_with = "date::deserialize_lenient")]
+    start_date: Option<date::Date>,
+    /// E.g. `Rust ecosystem` or `3D tools`.
+    category: String,
+}
+
+/// Spelled the same in the data file and in URLs, see `as_str`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum SponsorshipPlatform {
+    GitHub,
+    Patreon,
+    OpenCollective,
+    /// Donations made directly to the recipient, e.g. the Blender Development Fund.
+    Direct,
+}
+
+impl SponsorshipPlatform {
+    pub const ALL: [Self; 4] = [
+        Self::GitHub,
+        Self::Patreon,
+        Self::OpenCollective,
+        Self::Direct,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub Sponsors",
+            Self::Patreon => "Patreon",
+            Self::OpenCollective => "Open Collective",
+            Self::Direct => "Direct",
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::Patreon => "patreon",
+            Self::OpenCollective => "opencollective",
+            Self::Direct => "direct",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|platform| platform.as_str() == value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
     name: String,
@@ -75,25 +482,336 @@ pub struct Project {
     featured: bool,
     extended_description: Option<String>,
     feature_image: Option<String>,
+    #[serde(default)]
+    links: Vec<ProjectLink>,
+    /// GitHub stars, if known.
+    stars: Option<u32>,
+    /// When the project was added to the site, if known.
+    #[serde(default, deserialize_with = "date::deserialize_lenient")]
+    added: Option<date::Date>,
+    /// Position of `name` in the locale-aware order of all project names, set by
+    /// `rank_project_names`.
+    #[serde(skip)]
+    name_rank: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLink {
+    title: String,
+    url: String,
+}
+
+// ------ Content ------
+
+/// Texts of the home page sections, loaded from `CONTENT_URL`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Content {
+    hero: ContentSection,
+    sponsorship: ContentSection,
+    newsletter: ContentSection,
+    contribute: ContentSection,
+}
+
+/// Rendered in the order of the fields.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSection {
+    title: String,
+    subtitle: Option<String>,
+    /// Markdown.
+    body: String,
+    background: Option<Background>,
+    /// Sponsorship platforms, etc.
+    #[serde(default)]
+    logos: Vec<LogoLink>,
+    /// Links displayed as headings, e.g. "Read our Code of Conduct →".
+    #[serde(default)]
+    links: Vec<ContentLink>,
+    cta: Option<CallToAction>,
+    /// Markdown displayed after the call to action.
+    footer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoLink {
+    title: String,
+    url: String,
+    /// Path data of a 24x24 SVG icon; it's filled by CSS, so it follows the color scheme.
+    icon_path: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentLink {
+    label: String,
+    url: String,
+}
+
+// ------ DataStatus ------
+
+#[derive(Clone, Copy)]
+enum DataStatus {
+    /// `attempt` is 0 for the initial request and grows with every retry.
+    Loading {
+        attempt: u32,
+    },
+    Loaded,
+    Failed {
+        error: FetchErrorKind,
+        attempt: u32,
+    },
+}
+
+impl DataStatus {
+    const fn is_loading(self) -> bool {
+        matches!(self, Self::Loading { .. })
+    }
+
+    /// Marks the request as failed and returns the failed attempt;
+    /// `None` when cached data are displayed.
+    fn fail(&mut self, error: &fetch::FetchError) -> Option<u32> {
+        let attempt = match *self {
+            Self::Loading { attempt } => attempt,
+            // The automatic retry has failed.
+            Self::Failed { attempt, .. } => attempt + 1,
+            Self::Loaded => return None,
+        };
+        *self = Self::Failed {
+            error: FetchErrorKind::from(error),
+            attempt,
+        };
+        Some(attempt)
+    }
+
+    /// Starts the retry requested by the user; `false` unless the request has failed.
+    fn retry(&mut self) -> bool {
+        if let Self::Failed { attempt, .. } = *self {
+            *self = Self::Loading {
+                attempt: attempt + 1,
+            };
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FetchErrorKind {
+    Network,
+    Status(u16),
+    InvalidData,
+    Other,
+}
+
+impl From<&fetch::FetchError> for FetchErrorKind {
+    fn from(error: &fetch::FetchError) -> Self {
+        match error {
+            fetch::FetchError::NetworkError(_) => Self::Network,
+            fetch::FetchError::StatusError(status) => Self::Status(status.code),
+            fetch::FetchError::SerdeError(_) => Self::InvalidData,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl FetchErrorKind {
+    fn description(self) -> String {
+        match self {
+            Self::Network => "the server is unreachable".to_owned(),
+            Self::Status(code) => format!("the server responded with status {}", code),
+            Self::InvalidData => "the server sent invalid data".to_owned(),
+            Self::Other => "the request failed".to_owned(),
+        }
+    }
+}
+
+/// Delay of the automatic retry `attempt`; exponential backoff: 500 ms, 1 s, 2 s, ... up to 30 s.
+fn retry_delay_ms(attempt: u32) -> u32 {
+    RETRY_BASE_DELAY_MS
+        .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(RETRY_MAX_DELAY_MS)
 }
 
 // ------ Page ------
 
 enum Page {
-    Home,
-    Tags(Tag),
+    Home(ProjectSort),
+    Tags {
+        tags: BTreeSet<Tag>,
+        match_mode: TagMatch,
+        sort: ProjectSort,
+    },
+    TagIndex,
+    Project(String),
+    Search(String),
+    Newsletter,
+    /// Sponsorships on `platform`, or all of them.
+    Sponsorships {
+        platform: Option<SponsorshipPlatform>,
+        /// The requested platform when it isn't known; all sponsorships are displayed then.
+        unknown_platform: Option<String>,
+    },
+    /// The requested URL doesn't match any route.
+    NotFound(String),
 }
 
 impl Page {
     fn init(mut url: Url) -> Self {
-        let selected_tag = url
+        let requested_url = url.href();
+        let selected_tags = url
             .search_mut()
             .remove(TAGS_TAG_PARAMETER)
-            .and_then(|mut values| values.pop());
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tag| !tag.is_empty())
+            .collect::<BTreeSet<_>>();
+        let search_query = url
+            .search_mut()
+            .remove(SEARCH_QUERY_PARAMETER)
+            .and_then(|mut values| values.pop())
+            .unwrap_or_default();
+        let match_mode = url
+            .search_mut()
+            .remove(TAGS_MATCH_PARAMETER)
+            .and_then(|mut values| values.pop())
+            .and_then(|value| TagMatch::parse(&value))
+            .unwrap_or_default();
+        let sort = url
+            .search_mut()
+            .remove(SORT_PARAMETER)
+            .and_then(|mut values| values.pop())
+            .and_then(|value| ProjectSort::parse(&value))
+            .unwrap_or_default();
+        let requested_platform = url
+            .search_mut()
+            .remove(SPONSORSHIPS_PLATFORM_PARAMETER)
+            .and_then(|mut values| values.pop())
+            .filter(|value| !value.is_empty());
+        let platform = requested_platform
+            .as_deref()
+            .and_then(SponsorshipPlatform::parse);
+
+        match url.remaining_path_parts().as_slice() {
+            [TAGS] if selected_tags.is_empty() => Self::TagIndex,
+            [TAGS] => Self::Tags {
+                tags: selected_tags,
+                match_mode,
+                sort,
+            },
+            [PROJECTS, name] => Self::Project((*name).to_owned()),
+            [SEARCH] => Self::Search(search_query),
+            [NEWSLETTER] => Self::Newsletter,
+            [SPONSORSHIPS] => Self::Sponsorships {
+                platform,
+                unknown_platform: requested_platform.filter(|_| platform.is_none()),
+            },
+            [] => Self::Home(sort),
+            _ => Self::NotFound(requested_url),
+        }
+    }
+}
+
+// ------ TagMatch ------
+
+/// How the tags selected on the tags page are combined.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TagMatch {
+    /// Projects with at least one of the selected tags.
+    Any,
+    /// Projects with all of the selected tags.
+    All,
+}
+
+impl Default for TagMatch {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+impl TagMatch {
+    const ALL: [Self; 2] = [Self::Any, Self::All];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::All => "all",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "any" => Some(Self::Any),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    fn matches(self, project: &Project, tags: &BTreeSet<Tag>) -> bool {
+        match self {
+            Self::Any => project.tags.iter().any(|tag| tags.contains(tag)),
+            Self::All => tags.iter().all(|tag| project.tags.contains(tag)),
+        }
+    }
+}
+
+// ------ ProjectSort ------
+
+/// Order of project listings.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProjectSort {
+    Name,
+    /// The most recently added projects first.
+    RecentlyAdded,
+    /// Featured projects first, then by name.
+    Featured,
+    /// The most starred projects first; projects with unknown stars last.
+    Stars,
+}
+
+impl Default for ProjectSort {
+    fn default() -> Self {
+        Self::Name
+    }
+}
+
+impl ProjectSort {
+    pub const ALL: [Self; 4] = [Self::Name, Self::RecentlyAdded, Self::Featured, Self::Stars];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::RecentlyAdded => "recent",
+            Self::Featured => "featured",
+            Self::Stars => "stars",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::RecentlyAdded => "recently added",
+            Self::Featured => "featured first",
+            Self::Stars => "stars",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|sort| sort.as_str() == value)
+    }
 
-        match (url.remaining_path_parts().as_slice(), selected_tag) {
-            ([TAGS], Some(tag)) => Self::Tags(tag),
-            _ => Self::Home,
+    /// Search parameters for the sort; empty for the default one to keep links short.
+    fn search_parameters(self) -> Vec<(&'static str, Vec<&'static str>)> {
+        if self == Self::default() {
+            Vec::new()
+        } else {
+            vec![(SORT_PARAMETER, vec![self.as_str()])]
         }
     }
 }
@@ -107,10 +825,52 @@ impl<'a> Urls<'a> {
     pub fn home(self) -> Url {
         self.base_url().set_search(UrlSearch::default())
     }
-    pub fn tags(self, tag: &str) -> Url {
+    /// The home page with the project list in the given order.
+    pub fn project_list(self, sort: ProjectSort) -> Url {
+        self.base_url()
+            .set_search(UrlSearch::new(sort.search_parameters()))
+            .set_hash(PROJECT_LIST_FRAGMENT)
+    }
+    pub fn tags<T: AsRef<str>>(self, tags: &[T], match_mode: TagMatch, sort: ProjectSort) -> Url {
+        let mut search = vec![(
+            TAGS_TAG_PARAMETER,
+            tags.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+        )];
+        // `any` is the default, so single-tag links stay short.
+        if match_mode != TagMatch::default() {
+            search.push((TAGS_MATCH_PARAMETER, vec![match_mode.as_str()]));
+        }
+        search.extend(sort.search_parameters());
         self.base_url()
             .add_path_part(TAGS)
-            .set_search(UrlSearch::new(vec![(TAGS_TAG_PARAMETER, vec![tag])]))
+            .set_search(UrlSearch::new(search))
+    }
+    pub fn tag_index(self) -> Url {
+        self.base_url().add_path_part(TAGS)
+    }
+    pub fn project(self, name: &str) -> Url {
+        self.base_url().add_path_part(PROJECTS).add_path_part(name)
+    }
+    pub fn newsletter(self) -> Url {
+        self.base_url().add_path_part(NEWSLETTER)
+    }
+    pub fn sponsorships(self, platform: Option<SponsorshipPlatform>) -> Url {
+        let url = self.base_url().add_path_part(SPONSORSHIPS);
+        match platform {
+            Some(platform) => url.set_search(UrlSearch::new(vec![(
+                SPONSORSHIPS_PLATFORM_PARAMETER,
+                vec![platform.as_str()],
+            )])),
+            None => url,
+        }
+    }
+    pub fn search(self, query: &str) -> Url {
+        let url = self.base_url().add_path_part(SEARCH);
+        if query.is_empty() {
+            url
+        } else {
+            url.set_search(UrlSearch::new(vec![(SEARCH_QUERY_PARAMETER, vec![query])]))
+        }
     }
 }
 
@@ -120,46 +880,276 @@ impl<'a> Urls<'a> {
 
 pub enum Msg {
     UrlChanged(subs::UrlChanged),
-    DataFetched(fetch::Result<Data>),
+    UrlRequested(subs::UrlRequested),
+    DataFetched(fetch::Result<FetchedData>),
+    ContentFetched(fetch::Result<Content>),
+    RetryDataFetch,
+    RetryContentFetch,
     ToggleSearch,
+    OpenSearch,
     CloseSearch,
     SearchQueryChanged(String),
+    UpdateSearchResults,
+    ClearRecentSearches,
+    SelectNextSearchResult,
+    SelectPreviousSearchResult,
+    OpenSelectedSearchResult,
+    /// Tab (or Shift+Tab when `backwards`) pressed inside the search overlay.
+    CycleSearchFocus {
+        backwards: bool,
+    },
+    DismissDataIssues,
 }
 
 fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
         Msg::UrlChanged(subs::UrlChanged(url)) => {
+            // The previous page is still rendered, so its offset can be saved now.
+            let previous_url = mem::replace(&mut model.current_url, url.href());
+            if let Ok(offset) = window().scroll_y() {
+                model.scroll_positions.insert(previous_url, offset);
+            }
+            let saved_offset = if mem::take(&mut model.forward_navigation) {
+                None
+            } else {
+                model.scroll_positions.get(&model.current_url).copied()
+            };
+            let fragment = url.hash().cloned();
             model.page = Page::init(url);
+            if model.show_search {
+                // A link in the search overlay has been followed.
+                model.show_search = false;
+                recent_searches::add(&mut model.recent_searches, &model.search_query);
+            }
+            if let Page::Search(query) = &model.page {
+                model.search_query = query.clone();
+                schedule_search(model, orders);
+            }
 
-            window().scroll_to_with_scroll_to_options(web_sys::ScrollToOptions::new().top(0.));
+            orders.after_next_render(move |_| scroll_page(saved_offset, fragment.as_deref()));
+        }
+        Msg::UrlRequested(_) => model.forward_navigation = true,
+        Msg::DataFetched(Ok(FetchedData::NotModified)) => model.data_status = DataStatus::Loaded,
+        Msg::DataFetched(Ok(FetchedData::Modified { data, etag })) => {
+            cache::store(cache::DATA_KEY, &data, etag.clone());
+            model.data_etag = etag;
+            model.data_status = DataStatus::Loaded;
+            if data != model.data {
+                set_data(model, data);
+            }
         }
-        Msg::DataFetched(Ok(data)) => {
-            model.data = data;
-            model
-                .data
-                .projects
-                .sort_by_cached_key(|project| project.name.clone());
+        Msg::DataFetched(Err(error)) => {
+            if let Some(attempt) = model.data_status.fail(&error) {
+                error!(error);
+                // Retry automatically with backoff; `Msg::RetryDataFetch` retries right away.
+                let delay_ms = retry_delay_ms(attempt + 1);
+                model.data_request = Some(fetch_data(orders, delay_ms, model.data_etag.clone()));
+            } else {
+                // Keep showing the cached data; the next visit will revalidate them again.
+                error!("cannot revalidate data:", error);
+            }
         }
-        Msg::DataFetched(Err(error)) => error!(error),
+        Msg::RetryDataFetch => {
+            if model.data_status.retry() {
+                model.data_request = Some(fetch_data(orders, 0, model.data_etag.clone()));
+            }
+        }
+        Msg::ContentFetched(Ok(content)) => {
+            cache::store(cache::CONTENT_KEY, &content, None);
+            model.content_status = DataStatus::Loaded;
+            model.content = Some(content);
+        }
+        Msg::ContentFetched(Err(error)) => {
+            if let Some(attempt) = model.content_status.fail(&error) {
+                error!("cannot load content:", error);
+                model.content_request = Some(fetch_content(orders, retry_delay_ms(attempt + 1)));
+            } else {
+                error!("cannot revalidate content:", error);
+            }
+        }
+        Msg::RetryContentFetch => {
+            if model.content_status.retry() {
+                model.content_request = Some(fetch_content(orders, 0));
+            }
+        }
+        Msg::DismissDataIssues => model.data_issues.clear(),
+        search_msg => update_search(search_msg, model, orders),
+    }
+}
+
+/// Messages of the search overlay, its keyboard shortcuts and the search page input.
+fn update_search(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    match msg {
         Msg::ToggleSearch => {
             if model.show_search {
                 model.show_search = false;
             } else {
-                model.show_search = true;
-
-                let search_input_element = model.search_input_element.clone();
-                orders.after_next_render(move |_| {
-                    let input_element = search_input_element.get().expect("input_element");
-                    input_element.focus().expect("focus input_element");
-                    input_element.select();
-                });
+                open_search(model, orders);
+            }
+        }
+        Msg::OpenSearch => open_search(model, orders),
+        Msg::CloseSearch => {
+            model.show_search = false;
+            if !model.search_results.is_empty() {
+                recent_searches::add(&mut model.recent_searches, &model.search_query);
+            }
+        }
+        Msg::ClearRecentSearches => recent_searches::clear(&mut model.recent_searches),
+        Msg::SearchQueryChanged(query) => {
+            model.selected_search_result = 0;
+            // The search page stays in sync with the query without adding history entries.
+            // Other pages stay under the overlay; the search page is opened by submitting it.
+            if matches!(model.page, Page::Search(_)) {
+                let url = Urls::new(&model.base_url).search(&query);
+                url.go_and_replace();
+                model.current_url = url.href();
+                model.page = Page::Search(query.clone());
+            }
+            model.search_query = query;
+            schedule_search(model, orders);
+        }
+        Msg::UpdateSearchResults => {
+            model.search_timeout = None;
+            update_search_results(model);
+        }
+        Msg::SelectNextSearchResult => {
+            if model.selected_search_result + 1 < overlay_search_results(model).len() {
+                model.selected_search_result += 1;
+                orders.after_next_render(|_| scroll_to_selected_search_result());
+            }
+        }
+        Msg::SelectPreviousSearchResult => {
+            if model.selected_search_result > 0 {
+                model.selected_search_result -= 1;
+                orders.after_next_render(|_| scroll_to_selected_search_result());
+            }
+        }
+        Msg::OpenSelectedSearchResult => {
+            // Enter pressed before the debounced search has run.
+            if model.search_timeout.take().is_some() {
+                update_search_results(model);
             }
+            let selected_project = overlay_search_results(model)
+                .get(model.selected_search_result)
+                .and_then(|result| model.data.projects.get(result.project));
+            let url = match selected_project {
+                Some(project) => Urls::new(&model.base_url).project(&project.name),
+                // Submitting a query without results opens the search page.
+                None if !model.search_query.trim().is_empty() => {
+                    Urls::new(&model.base_url).search(&model.search_query)
+                }
+                None => return,
+            };
+            model.show_search = false;
+            recent_searches::add(&mut model.recent_searches, &model.search_query);
+            orders.request_url(url);
         }
-        Msg::CloseSearch => model.show_search = false,
-        Msg::SearchQueryChanged(query) => model.search_query = query,
+        Msg::CycleSearchFocus { backwards } => cycle_focus(SEARCH_OVERLAY_FOCUSABLE, backwards),
+        // Handled by `update`.
+        _ => (),
     }
 }
 
+fn event_target_is_input(event: &web_sys::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .is_some()
+}
+
+/// Debounces `update_search_results`; the previous timeout is aborted when its handle drops.
+fn schedule_search(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    model.search_timeout = Some(
+        orders.perform_cmd_with_handle(cmds::timeout(SEARCH_DEBOUNCE_MS, || {
+            Msg::UpdateSearchResults
+        })),
+    );
+}
+
+fn update_search_results(model: &mut Model) {
+    model.search_results = model.search_index.search(&model.search_query, &model.data);
+    let last_result = model.search_results.len().saturating_sub(1);
+    model.selected_search_result = model.selected_search_result.min(last_result);
+}
+
+/// Results listed in the search overlay; an empty query shows suggestions instead.
+fn overlay_search_results(model: &Model) -> &[search::SearchResult] {
+    if model.search_query.trim().is_empty() {
+        &[]
+    } else {
+        &model.search_results
+    }
+}
+
+fn open_search(model: &mut Model, orders: &mut impl Orders<Msg>) {
+    model.show_search = true;
+
+    let search_input_element = model.search_input_element.clone();
+    orders.after_next_render(move |_| {
+        let input_element = search_input_element.get().expect("input_element");
+        input_element.focus().expect("focus input_element");
+        input_element.select();
+    });
+}
+
+fn scroll_to_selected_search_result() {
+    if let Ok(Some(element)) = document().query_selector(".search-result--selected") {
+        element.scroll_into_view_with_bool(false);
+    }
+}
+
+/// Moves the focus to the next (or previous) element matching `selector`, wrapping around,
+/// so the focus can't leave a dialog.
+fn cycle_focus(selector: &str, backwards: bool) {
+    let elements = match document().query_selector_all(selector) {
+        Ok(elements) => elements,
+        Err(_) => return,
+    };
+    let elements = (0..elements.length())
+        .filter_map(|index| elements.get(index))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect::<Vec<_>>();
+    if elements.is_empty() {
+        return;
+    }
+    let active_element = document()
+        .active_element()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    let current = elements
+        .iter()
+        .position(|element| active_element.as_ref() == Some(element));
+    let next = match current {
+        Some(index) if backwards && index > 0 => index - 1,
+        _ if backwards => elements.len() - 1,
+        Some(index) if index + 1 < elements.len() => index + 1,
+        _ => 0,
+    };
+    elements[next].focus().ok();
+}
+
+/// Scrolls to the saved `offset`, or to the element targeted by `fragment`, or to the top.
+fn scroll_page(offset: Option<f64>, fragment: Option<&str>) {
+    let target = fragment.and_then(|fragment| document().get_element_by_id(fragment));
+    match (offset, target) {
+        (None, Some(target)) => target.scroll_into_view(),
+        (offset, _) => window().scroll_to_with_scroll_to_options(
+            web_sys::ScrollToOptions::new().top(offset.unwrap_or_default()),
+        ),
+    }
+}
+
+fn set_data(model: &mut Model, data: Data) {
+    document().set_title(&data.site.title);
+    model.data_issues = validation::validate(&data);
+    for issue in &model.data_issues {
+        error!("invalid data:", issue.to_string());
+    }
+    model.data = data;
+    rank_project_names(&mut model.data.projects);
+    model.search_index = search::SearchIndex::new(&model.data);
+    update_search_results(model);
+}
+
 // ------ ------
 // View Helpers
 // ------ ------
@@ -176,58 +1166,181 @@ pub fn iter_projects_by_tag<'a>(
     })
 }
 
+pub fn iter_projects_by_tags<'a>(
+    projects: &'a [Project],
+    tags: &'a BTreeSet<Tag>,
+    match_mode: TagMatch,
+) -> impl Iterator<Item = &'a Project> {
+    projects
+        .iter()
+        .filter(move |project| match_mode.matches(project, tags))
+}
+
+/// `projects` in the given order. `Data.projects` keep the order of the data file.
+pub fn sort_projects<'a>(
+    projects: impl IntoIterator<Item = &'a Project>,
+    sort: ProjectSort,
+) -> Vec<&'a Project> {
+    let mut projects = projects.into_iter().collect::<Vec<_>>();
+    projects.sort_by_key(|project| project.name_rank);
+    // Stable sorts keep projects with equal keys ordered by name.
+    match sort {
+        ProjectSort::Featured => projects.sort_by_key(|project| !project.featured),
+        ProjectSort::Stars => projects.sort_by_key(|project| Reverse(project.stars)),
+        // `Reverse(None)` is the greatest key, so projects with unknown dates come last.
+        ProjectSort::RecentlyAdded => projects.sort_by_key(|project| Reverse(project.added)),
+        ProjectSort::Name => (),
+    }
+    projects
+}
+
+/// Sets `Project::name_rank`, so listings don't have to collate names on every render.
+fn rank_project_names(projects: &mut [Project]) {
+    let compare_names = name_comparator();
+    let mut order = (0..projects.len()).collect::<Vec<_>>();
+    order.sort_by(|&index_a, &index_b| {
+        compare_names(&projects[index_a].name, &projects[index_b].name)
+    });
+    for (rank, index) in order.into_iter().enumerate() {
+        projects[index].name_rank = rank;
+    }
+}
+
+/// Locale-aware comparison, so e.g. lowercase and capitalized names aren't separated.
+#[cfg(target_arch = "wasm32")]
+fn name_comparator() -> impl Fn(&str, &str) -> Ordering {
+    let compare =
+        js_sys::Intl::Collator::new(&js_sys::Array::new(), &js_sys::Object::new()).compare();
+    move |name_a, name_b| {
+        compare
+            .call2(&JsValue::NULL, &name_a.into(), &name_b.into())
+            .ok()
+            .and_then(|ordering| ordering.as_f64())
+            .and_then(|ordering| ordering.partial_cmp(&0.))
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// `Intl` collation is only available in the browser. Like its default collation, this one
+/// ignores case, except that lowercase letters come first between otherwise equal names, so
+/// prerendered pages list projects in the same order as the app.
+#[cfg(not(target_arch = "wasm32"))]
+fn name_comparator() -> impl Fn(&str, &str) -> Ordering {
+    |name_a, name_b| {
+        name_a
+            .to_lowercase()
+            .cmp(&name_b.to_lowercase())
+            .then_with(|| {
+                let uppercase =
+                    |name: &str| name.chars().map(char::is_uppercase).collect::<Vec<_>>();
+                uppercase(name_a).cmp(&uppercase(name_b))
+            })
+            .then_with(|| name_a.cmp(name_b))
+    }
+}
+
+/// Tags used by `projects` with their project counts, the most used first.
+pub fn tag_counts(projects: &[Project]) -> Vec<(&str, usize)> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for tag in projects.iter().flat_map(|project| &project.tags) {
+        *counts.entry(tag).or_default() += 1;
+    }
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    // Stable sort keeps tags with the same count in alphabetical order.
+    counts.sort_by(|(_, count_a), (_, count_b)| count_b.cmp(count_a));
+    counts
+}
+
 // ------ ------
 //     View
 // ------ ------
 
 fn view(model: &Model) -> Vec<Node<Msg>> {
-    let projects = &model.data.projects;
+    let data = &model.data;
     let base_url = &model.base_url;
+    let loading = model.data_status.is_loading();
 
-    let search_results = projects.iter().filter(|project| {
-        project
-            .name
-            .to_lowercase()
-            .contains(&model.search_query.to_lowercase())
-    });
+    // Data issues are meant for content editors, so they're hidden in release builds.
+    let show_data_issues = cfg!(debug_assertions) && !model.data_issues.is_empty();
 
     nodes![
-        view_header(),
-        view_search_overlay(
-            model.show_search,
-            &model.search_query,
-            search_results,
-            &model.search_input_element,
-            base_url,
-        ),
+        IF!(show_data_issues => view_data_issues(&model.data_issues)),
+        view_header(&data.site, base_url),
+        if let DataStatus::Failed { error, attempt } = model.data_status {
+            view_fetch_error("Projects couldn't be loaded", error, attempt, || {
+                Msg::RetryDataFetch
+            })
+        } else {
+            empty![]
+        },
+        match (&model.page, model.content_status) {
+            (Page::Home(_), DataStatus::Failed { error, attempt }) => {
+                view_fetch_error("Texts couldn't be loaded", error, attempt, || {
+                    Msg::RetryContentFetch
+                })
+            }
+            _ => empty![],
+        },
+        view_search_overlay(model),
         match &model.page {
-            Page::Home => page::home::view(projects, base_url),
-            Page::Tags(tag) => page::tags::view(tag, projects, base_url),
+            Page::Home(sort) =>
+                page::home::view(data, model.content.as_ref(), *sort, loading, base_url),
+            Page::Tags {
+                tags,
+                match_mode,
+                sort,
+            } => page::tags::view(tags, *match_mode, *sort, data, loading, base_url),
+            Page::TagIndex => page::tag_index::view(data, loading, base_url),
+            Page::Project(name) => page::project::view(name, data, loading, base_url),
+            Page::Search(query) => page::search::view(
+                query,
+                &model.search_results,
+                &model.search_index,
+                data,
+                loading,
+                base_url,
+            ),
+            Page::Newsletter => page::newsletter::view(data, loading, base_url),
+            Page::Sponsorships {
+                platform,
+                unknown_platform,
+            } => page::sponsorships::view(
+                *platform,
+                unknown_platform.as_deref(),
+                data,
+                loading,
+                base_url
+            ),
+            Page::NotFound(requested_url) => {
+                page::not_found::view(requested_url, data, loading, base_url)
+            }
         }
     ]
 }
 
-fn view_header() -> Node<Msg> {
+fn view_header(site: &SiteConfig, base_url: &Url) -> Node<Msg> {
     header![
         C!["header"],
         a![
-            attrs! {At::Href => "https://embark-studios.com"},
-            img![id!("logo"), attrs! {At::Src => "/public/img/logo.png"}]
+            attrs! {At::Href => &site.homepage},
+            IF!(!site.logo.is_empty() => img![id!("logo"), attrs! {At::Src => &site.logo}])
         ],
         div![
+            site.social_links.iter().map(|link| {
+                vec![
+                    a![
+                        C!["fa", format!("fa-{}", link.icon)],
+                        attrs! {At::Href => &link.url}
+                    ],
+                    Node::new_text(" "),
+                ]
+            }),
             a![
-                C!["fa", "fa-globe"],
-                attrs! {At::Href => "https://embark-studios.com"}
-            ],
-            " ",
-            a![
-                C!["fa", "fa-twitter"],
-                attrs! {At::Href => "https://twitter.com/EmbarkStudios"}
-            ],
-            " ",
-            a![
-                C!["fa", "fa-github"],
-                attrs! {At::Href => "https://github.com/EmbarkStudios"}
+                C!["fa", "fa-tags"],
+                attrs! {
+                    At::Href => Urls::new(base_url).tag_index().href(),
+                    At::Title => "All tags",
+                }
             ],
             " ",
             a![
@@ -242,13 +1355,50 @@ fn view_header() -> Node<Msg> {
     ]
 }
 
-fn view_search_overlay<'a>(
-    show_search: bool,
-    search_query: &str,
-    search_results: impl Iterator<Item = &'a Project>,
-    search_input_element: &ElRef<web_sys::HtmlInputElement>,
-    base_url: &Url,
+fn view_data_issues(issues: &[validation::Issue]) -> Node<Msg> {
+    div![
+        C!["data-issues"],
+        span![
+            C!["fa", "fa-close", "data-issues__close"],
+            ev(Ev::Click, |_| Msg::DismissDataIssues)
+        ],
+        h3![format!("data.json contains {} issue(s)", issues.len())],
+        ul![issues.iter().map(|issue| li![issue.to_string()])]
+    ]
+}
+
+/// `retry` is sent by the button retrying the failed request right away.
+fn view_fetch_error(
+    heading: &str,
+    error: FetchErrorKind,
+    attempt: u32,
+    retry: fn() -> Msg,
 ) -> Node<Msg> {
+    let retry_delay_s = (retry_delay_ms(attempt + 1) + 999) / 1000;
+    section![
+        C!["data-error"],
+        div![
+            C!["container"],
+            h2![heading],
+            p![format!(
+                "Sorry, {}. Retrying in {} s.",
+                error.description(),
+                retry_delay_s
+            )],
+            button![
+                C!["button-primary"],
+                ev(Ev::Click, move |_| retry()),
+                "Retry now"
+            ]
+        ]
+    ]
+}
+
+fn view_search_overlay(model: &Model) -> Node<Msg> {
+    let data = &model.data;
+    let base_url = &model.base_url;
+    let search_query = &model.search_query;
+
     div![
         C!["search-overlay"],
         // @TODO remove style! below ; custom style to see at least something
@@ -259,12 +1409,23 @@ fn view_search_overlay<'a>(
             St::OverflowY => "auto",
         },
         style! {
-            St::Display => if show_search { "block" } else { "none" },
+            St::Display => if model.show_search { "block" } else { "none" },
         },
-        keyboard_ev(
-            Ev::KeyUp,
-            |event| IF!(event.key() == "Escape" => Msg::CloseSearch)
-        ),
+        keyboard_ev(Ev::KeyDown, |event| {
+            let msg = match event.key().as_str() {
+                "Escape" => Msg::CloseSearch,
+                "ArrowDown" => Msg::SelectNextSearchResult,
+                "ArrowUp" => Msg::SelectPreviousSearchResult,
+                // Focused links keep their default behavior.
+                "Enter" if event_target_is_input(&event) => Msg::OpenSelectedSearchResult,
+                "Tab" => Msg::CycleSearchFocus {
+                    backwards: event.shift_key(),
+                },
+                _ => return None,
+            };
+            event.prevent_default();
+            Some(msg)
+        }),
         div![
             C!["search-overlay__content"],
             span![
@@ -273,7 +1434,7 @@ fn view_search_overlay<'a>(
             ],
             input![
                 id!("search-input"),
-                el_ref(search_input_element),
+                el_ref(&model.search_input_element),
                 attrs! {
                     At::Type => "text",
                     At::Placeholder => "Start typing...",
@@ -281,14 +1442,158 @@ fn view_search_overlay<'a>(
                 },
                 input_ev(Ev::Input, Msg::SearchQueryChanged),
             ],
-            div![
-                C!["search-overlay__results"],
-                search_results.map(|project| page::partial::view_project(project, base_url))
-            ]
+            page::partial::view_query_errors(search_query),
+            if search_query.trim().is_empty() {
+                view_search_suggestions(&model.recent_searches, data, base_url)
+            } else if model.search_results.is_empty() {
+                view_no_search_results(search_query, data)
+            } else {
+                div![
+                    C!["search-overlay__results"],
+                    model
+                        .search_results
+                        .iter()
+                        .enumerate()
+                        .map(|(index, result)| {
+                            page::partial::view_search_result(
+                                result,
+                                &model.search_index,
+                                index == model.selected_search_result,
+                                data,
+                                base_url,
+                            )
+                        }),
+                    p![
+                        C!["search-overlay__all-results"],
+                        a![
+                            attrs! {At::Href => Urls::new(base_url).search(search_query).href()},
+                            "All results →"
+                        ]
+                    ]
+                ]
+            }
         ]
     ]
 }
 
+/// Recent searches, popular tags and featured projects offered before anything is typed.
+fn view_search_suggestions(recent_searches: &[String], data: &Data, base_url: &Url) -> Node<Msg> {
+    let featured_projects = sort_projects(
+        data.projects.iter().filter(|project| project.featured),
+        ProjectSort::Name,
+    );
+
+    div![
+        C!["search-suggestions"],
+        IF!(!recent_searches.is_empty() => div![
+            h4![
+                "Recent searches ",
+                a![
+                    C!["search-suggestions__clear"],
+                    attrs! {At::Href => "#"},
+                    "Clear",
+                    ev(Ev::Click, |event| {
+                        event.prevent_default();
+                        Msg::ClearRecentSearches
+                    })
+                ],
+            ],
+            ul![
+                C!["search-suggestions__recent"],
+                recent_searches.iter().map(|recent_search| {
+                    let query = recent_search.clone();
+                    li![a![
+                        attrs! {At::Href => "#"},
+                        i![C!["fa", "fa-history"]],
+                        " ",
+                        recent_search,
+                        ev(Ev::Click, move |event| {
+                            event.prevent_default();
+                            Msg::SearchQueryChanged(query)
+                        })
+                    ]]
+                })
+            ]
+        ]),
+        h4!["Popular tags"],
+        view_tag_query_suggestions(
+            tag_counts(&data.projects)
+                .into_iter()
+                .take(POPULAR_TAG_COUNT)
+                .map(|(tag, _)| tag),
+            data
+        ),
+        IF!(!featured_projects.is_empty() => div![
+            h4!["Featured projects"],
+            ul![
+                C!["search-suggestions__featured"],
+                featured_projects.into_iter().map(|project| {
+                    li![a![
+                        attrs! {At::Href => Urls::new(base_url).project(&project.name).href()},
+                        &project.emoji,
+                        " ",
+                        &project.name
+                    ]]
+                })
+            ]
+        ]),
+    ]
+}
+
+fn view_no_search_results(search_query: &str, data: &Data) -> Node<Msg> {
+    let tags = tag_counts(&data.projects)
+        .into_iter()
+        .map(|(tag, _)| tag)
+        .collect::<Vec<_>>();
+    // Several terms may suggest the same tag; keep the first suggestion only.
+    let mut suggested_tags = Vec::new();
+    for term in &query::parse(search_query).terms {
+        if let Some(tag) = suggest::closest(term, tags.iter().copied()) {
+            if !suggested_tags.contains(&tag) {
+                suggested_tags.push(tag);
+            }
+        }
+    }
+    if suggested_tags.is_empty() {
+        suggested_tags = tags.into_iter().take(POPULAR_TAG_COUNT).collect();
+    }
+
+    div![
+        C!["search-suggestions"],
+        p![
+            C!["search-suggestions__empty"],
+            "No projects match ",
+            b![search_query],
+            "."
+        ],
+        h4!["Try one of these tags"],
+        view_tag_query_suggestions(suggested_tags.into_iter(), data),
+    ]
+}
+
+/// Tags which replace the search query with a `tag:` filter when clicked.
+fn view_tag_query_suggestions<'a>(tags: impl Iterator<Item = &'a str>, data: &Data) -> Node<Msg> {
+    div![
+        C!["tags"],
+        tags.map(|tag| {
+            let query = format!("tag:{}", tag);
+            page::partial::view_tag(
+                tag,
+                data,
+                a![
+                    attrs! {At::Href => "#"},
+                    data.tag_info(tag).and_then(page::partial::view_tag_icon),
+                    data.tag_label(tag),
+                    ev(Ev::Click, move |event| {
+                        event.prevent_default();
+                        Msg::SearchQueryChanged(query)
+                    })
+                ],
+            )
+        })
+    ]
+}
+
 // ------ ------
 //     Start
 // ------ ------
@@ -297,3 +1602,145 @@ fn view_search_overlay<'a>(
 pub fn start() {
     App::start("app", init, update, view);
 }
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(path: &[&str], search: Vec<(&str, Vec<&str>)>) -> Url {
+        Url::new().set_path(path).set_search(UrlSearch::new(search))
+    }
+
+    /// Projects named `names`, added on the given dates, with their names ranked.
+    fn projects(names_and_dates: &[(&str, Option<&str>)]) -> Vec<Project> {
+        let projects = names_and_dates
+            .iter()
+            .map(|(name, added)| {
+                let mut project = test_data::project(name, &["rust"]);
+                project["added"] = serde_json::json!(added);
+                project
+            })
+            .collect::<Vec<_>>();
+        let mut data = test_data::data(test_data::rust_tag(), serde_json::json!(projects));
+        rank_project_names(&mut data.projects);
+        data.projects
+    }
+
+    fn names(projects: &[&Project]) -> Vec<String> {
+        projects
+            .iter()
+            .map(|project| project.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn sort_by_name_ignores_case() {
+        let projects = projects(&[
+            ("physx", None),
+            ("Ash", None),
+            ("cargo-deny", None),
+            ("ash", None),
+            ("Cargo", None),
+        ]);
+        assert_eq!(
+            names(&sort_projects(&projects, ProjectSort::Name)),
+            vec!["ash", "Ash", "Cargo", "cargo-deny", "physx"]
+        );
+    }
+
+    #[test]
+    fn sort_by_date_puts_unknown_dates_last() {
+        let projects = projects(&[
+            ("physx", None),
+            ("ash", Some("2019-06-12")),
+            ("cargo-deny", None),
+            ("texture-synthesis", Some("2020-01-01")),
+        ]);
+        assert_eq!(
+            names(&sort_projects(&projects, ProjectSort::RecentlyAdded)),
+            vec!["texture-synthesis", "ash", "cargo-deny", "physx"]
+        );
+    }
+
+    #[test]
+    fn tag_index_route() {
+        assert!(matches!(Page::init(url(&[TAGS], vec![])), Page::TagIndex));
+        // Empty tags select nothing.
+        assert!(matches!(
+            Page::init(url(&[TAGS], vec![(TAGS_TAG_PARAMETER, vec![""])])),
+            Page::TagIndex
+        ));
+    }
+
+    #[test]
+    fn tags_route() {
+        let page = Page::init(url(
+            &[TAGS],
+            vec![
+                (TAGS_TAG_PARAMETER, vec!["rust", "go"]),
+                (TAGS_MATCH_PARAMETER, vec!["all"]),
+                (SORT_PARAMETER, vec!["stars"]),
+            ],
+        ));
+        assert!(matches!(
+            page,
+            Page::Tags {
+                tags,
+                match_mode: TagMatch::All,
+                sort: ProjectSort::Stars,
+            } if tags.iter().eq(&["go", "rust"])
+        ));
+    }
+
+    #[test]
+    fn tags_route_defaults() {
+        let page = Page::init(url(
+            &[TAGS],
+            vec![
+                (TAGS_TAG_PARAMETER, vec!["rust"]),
+                (TAGS_MATCH_PARAMETER, vec!["most"]),
+                (SORT_PARAMETER, vec!["size"]),
+            ],
+        ));
+        assert!(matches!(
+            page,
+            Page::Tags {
+                match_mode: TagMatch::Any,
+                sort: ProjectSort::Name,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn search_route() {
+        let url = url(
+            &[SEARCH],
+            vec![(SEARCH_QUERY_PARAMETER, vec!["tag:rust -ash"])],
+        );
+        assert!(matches!(Page::init(url), Page::Search(query) if query == "tag:rust -ash"));
+        assert!(matches!(
+            Page::init(Urls::new(&Url::new()).search("")),
+            Page::Search(query) if query.is_empty()
+        ));
+    }
+
+    #[test]
+    fn unknown_routes_are_not_found() {
+        for path in &[
+            &["tag"][..],
+            &[PROJECTS],
+            &[PROJECTS, "ash", "extra"],
+            &[TAGS, "rust"],
+        ] {
+            assert!(matches!(
+                Page::init(url(path, vec![])),
+                Page::NotFound(requested_url) if requested_url == format!("/{}", path.join("/"))
+            ));
+        }
+    }
+}
@@ -0,0 +1,516 @@
+//! Renderer for the safe Markdown subset used in `data.json` and `content.json`.
+//!
+//! Supported: paragraphs, ordered and unordered lists, inline code, links, emphasis and
+//! the inline HTML tags allowed by `sanitize`. Everything is converted to Seed nodes,
+//! so text is always escaped and no raw HTML reaches the DOM.
+
+use crate::sanitize::{self, Tag};
+use seed::{prelude::*, *};
+
+const LINK_REL: &str = "noopener noreferrer";
+
+pub fn view_markdown<Ms>(source: &str) -> Vec<Node<Ms>> {
+    parse_blocks(source).iter().map(view_block).collect()
+}
+
+/// Text content of `source` without markup, blocks and list items separated by spaces.
+pub fn plain_text(source: &str) -> String {
+    let mut text = String::new();
+    for block in parse_blocks(source) {
+        let items = match block {
+            Block::Paragraph(paragraph) => vec![paragraph],
+            Block::UnorderedList(items) | Block::OrderedList(items) => items,
+        };
+        for item in items {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            push_plain_text(&parse_inlines(&item), &mut text);
+        }
+    }
+    text
+}
+
+// ------ Blocks ------
+
+enum Block {
+    Paragraph(String),
+    UnorderedList(Vec<String>),
+    OrderedList(Vec<String>),
+}
+
+fn parse_blocks(source: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blocks.extend(current.take());
+            continue;
+        }
+        let is_continuation = line.starts_with(char::is_whitespace);
+
+        current = match (current, list_item(trimmed)) {
+            (Some(Block::UnorderedList(mut items)), Some((false, item))) => {
+                items.push(item.to_owned());
+                Some(Block::UnorderedList(items))
+            }
+            (Some(Block::OrderedList(mut items)), Some((true, item))) => {
+                items.push(item.to_owned());
+                Some(Block::OrderedList(items))
+            }
+            (previous, Some((ordered, item))) if !is_continuation || previous.is_none() => {
+                blocks.extend(previous);
+                let items = vec![item.to_owned()];
+                Some(if ordered {
+                    Block::OrderedList(items)
+                } else {
+                    Block::UnorderedList(items)
+                })
+            }
+            (Some(Block::Paragraph(mut text)), _) => {
+                text.push(' ');
+                text.push_str(trimmed);
+                Some(Block::Paragraph(text))
+            }
+            (Some(Block::UnorderedList(mut items)), _) if is_continuation => {
+                append_to_last_item(&mut items, trimmed);
+                Some(Block::UnorderedList(items))
+            }
+            (Some(Block::OrderedList(mut items)), _) if is_continuation => {
+                append_to_last_item(&mut items, trimmed);
+                Some(Block::OrderedList(items))
+            }
+            (previous, _) => {
+                blocks.extend(previous);
+                Some(Block::Paragraph(trimmed.to_owned()))
+            }
+        };
+    }
+    blocks.extend(current);
+    blocks
+}
+
+/// Returns `(is_ordered, item_text)` when `line` starts a list item.
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(item) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some((false, item.trim_start()));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .map(|item| (true, item.trim_start()))
+}
+
+fn append_to_last_item(items: &mut [String], line: &str) {
+    if let Some(item) = items.last_mut() {
+        item.push(' ');
+        item.push_str(line);
+    }
+}
+
+fn view_block<Ms>(block: &Block) -> Node<Ms> {
+    match block {
+        Block::Paragraph(text) => p![view_inlines(&parse_inlines(text))],
+        Block::UnorderedList(items) => ul![items
+            .iter()
+            .map(|item| li![view_inlines(&parse_inlines(item))])],
+        Block::OrderedList(items) => ol![items
+            .iter()
+            .map(|item| li![view_inlines(&parse_inlines(item))])],
+    }
+}
+
+// ------ Inlines ------
+
+enum Inline {
+    Text(String),
+    Code(String),
+    Emphasis(Vec<Self>),
+    Strong(Vec<Self>),
+    Link {
+        href: Option<String>,
+        children: Vec<Self>,
+    },
+}
+
+fn parse_inlines(source: &str) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    let mut text = String::new();
+    let mut previous_char = None;
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '\\' => parse_escape(rest),
+            '`' => parse_code_span(rest),
+            '[' => parse_link(rest),
+            '*' | '_' => parse_emphasis(rest, previous_char),
+            '<' => parse_html(rest),
+            _ => None,
+        };
+        if let Some((parsed_inlines, len)) = parsed {
+            flush_text(&mut text, &mut inlines);
+            inlines.extend(parsed_inlines);
+            previous_char = rest[..len].chars().last();
+            rest = &rest[len..];
+        } else {
+            text.push(c);
+            previous_char = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    flush_text(&mut text, &mut inlines);
+    inlines
+}
+
+/// Parsers below return the parsed inlines and the number of consumed bytes.
+type Parsed = Option<(Vec<Inline>, usize)>;
+
+fn parse_escape(input: &str) -> Parsed {
+    let escaped = input[1..]
+        .chars()
+        .next()
+        .filter(char::is_ascii_punctuation)?;
+    Some((vec![Inline::Text(escaped.to_string())], 2))
+}
+
+fn parse_code_span(input: &str) -> Parsed {
+    let ticks = input.chars().take_while(|c| *c == '`').count();
+    let fence = &input[..ticks];
+    let content_len = input[ticks..].find(fence)?;
+    let code = &input[ticks..ticks + content_len];
+    let code = code
+        .strip_prefix(' ')
+        .and_then(|code| code.strip_suffix(' '))
+        .unwrap_or(code);
+    Some((vec![Inline::Code(code.to_owned())], ticks * 2 + content_len))
+}
+
+fn parse_link(input: &str) -> Parsed {
+    let mut depth = 0;
+    let mut text_end = None;
+    let mut chars = input.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    text_end = Some(index);
+                    break;
+                }
+            }
+            _ => (),
+        }
+    }
+    let text_end = text_end?;
+    let destination = input[text_end + 1..].strip_prefix('(')?;
+    let destination_len = closing_parenthesis(destination)?;
+    // An optional title (`[text](url "title")`) is ignored.
+    let url = destination[..destination_len].split_whitespace().next()?;
+    let url = sanitize::decode_entities(url.trim_start_matches('<').trim_end_matches('>'));
+
+    let link = Inline::Link {
+        href: Some(url).filter(|url| sanitize::is_safe_url(url)),
+        children: parse_inlines(&input[1..text_end]),
+    };
+    Some((vec![link], text_end + 2 + destination_len + 1))
+}
+
+/// Position of the `)` closing the link destination; nested parentheses are balanced.
+fn closing_parenthesis(destination: &str) -> Option<usize> {
+    let mut depth = 0_usize;
+    for (index, c) in destination.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(index),
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+fn parse_emphasis(input: &str, previous_char: Option<char>) -> Parsed {
+    let delimiter = input.chars().next()?;
+    // `snake_case_names` must stay intact.
+    if delimiter == '_' && matches!(previous_char, Some(c) if c.is_alphanumeric()) {
+        return None;
+    }
+    let strong = input[1..].starts_with(delimiter);
+    let delimiter_len = if strong { 2 } else { 1 };
+    let closing = &input[..delimiter_len];
+
+    let content = &input[delimiter_len..];
+    if content.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut search_from = 0;
+    let content_len = loop {
+        let candidate = search_from + content[search_from..].find(closing)?;
+        let after = &content[candidate + delimiter_len..];
+        let valid = candidate > 0
+            && !content[..candidate].ends_with(char::is_whitespace)
+            // A single delimiter mustn't close on the first half of a double one.
+            && (strong || !after.starts_with(delimiter))
+            && !(delimiter == '_' && after.starts_with(char::is_alphanumeric));
+        if valid {
+            break candidate;
+        }
+        search_from = candidate + delimiter_len;
+    };
+
+    let children = parse_inlines(&content[..content_len]);
+    let inline = if strong {
+        Inline::Strong(children)
+    } else {
+        Inline::Emphasis(children)
+    };
+    Some((vec![inline], content_len + delimiter_len * 2))
+}
+
+/// Inline HTML goes through the same allow-list as `sanitize`. Other tags are shown as text
+/// (e.g. `Vec<u8>`), except tags like `script` that are dropped with their content.
+fn parse_html(input: &str) -> Parsed {
+    let (tag, tag_len) = sanitize::parse_tag(input)?;
+    let rest = &input[tag_len..];
+
+    let (name, href) = match tag {
+        Tag::Start { name, .. } if sanitize::is_dropped_content_tag(&name) => {
+            let remaining = sanitize::skip_to_end_tag(rest, &name);
+            return Some((Vec::new(), input.len() - remaining.len()));
+        }
+        Tag::Start { name, href } if sanitize::is_allowed_tag(&name) => (name, href),
+        // Comments, declarations and processing instructions.
+        Tag::Other => return Some((Vec::new(), tag_len)),
+        Tag::Start { .. } | Tag::End { .. } => return None,
+    };
+
+    let end_tag = format!("</{}>", name);
+    // Unclosed tags are shown as text as well.
+    let content_len = rest.to_ascii_lowercase().find(&end_tag)?;
+    let content = &rest[..content_len];
+
+    let inline = match name.as_str() {
+        "a" => Inline::Link {
+            href,
+            children: parse_inlines(content),
+        },
+        "code" => Inline::Code(sanitize::decode_entities(content)),
+        "b" | "strong" => Inline::Strong(parse_inlines(content)),
+        _ => Inline::Emphasis(parse_inlines(content)),
+    };
+    Some((vec![inline], tag_len + content_len + end_tag.len()))
+}
+
+fn flush_text(text: &mut String, inlines: &mut Vec<Inline>) {
+    if !text.is_empty() {
+        inlines.push(Inline::Text(sanitize::decode_entities(text)));
+        text.clear();
+    }
+}
+
+fn push_plain_text(inlines: &[Inline], text: &mut String) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(inline_text) | Inline::Code(inline_text) => text.push_str(inline_text),
+            Inline::Emphasis(children) | Inline::Strong(children) => {
+                push_plain_text(children, text)
+            }
+            Inline::Link { children, .. } => push_plain_text(children, text),
+        }
+    }
+}
+
+fn view_inlines<Ms>(inlines: &[Inline]) -> Vec<Node<Ms>> {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => Node::new_text(text.clone()),
+            Inline::Code(code) => code![code],
+            Inline::Emphasis(children) => em![view_inlines(children)],
+            Inline::Strong(children) => strong![view_inlines(children)],
+            Inline::Link { href, children } => a![
+                href.as_ref().map(|href| attrs! {
+                    At::Href => href,
+                    At::Rel => LINK_REL,
+                }),
+                view_inlines(children),
+            ],
+        })
+        .collect()
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    /// HTML-like markup of the inlines parsed from `source`, escaping only `<`.
+    fn markup(source: &str) -> String {
+        fn push_markup(inlines: &[Inline], markup: &mut String) {
+            for inline in inlines {
+                match inline {
+                    Inline::Text(text) => markup.push_str(&text.replace('<', "&lt;")),
+                    Inline::Code(code) => {
+                        markup.push_str("<code>");
+                        markup.push_str(&code.replace('<', "&lt;"));
+                        markup.push_str("</code>");
+                    }
+                    Inline::Emphasis(children) => {
+                        markup.push_str("<em>");
+                        push_markup(children, markup);
+                        markup.push_str("</em>");
+                    }
+                    Inline::Strong(children) => {
+                        markup.push_str("<strong>");
+                        push_markup(children, markup);
+                        markup.push_str("</strong>");
+                    }
+                    Inline::Link { href, children } => {
+                        match href {
+                            Some(href) => write!(markup, r#"<a href="{}">"#, href).unwrap(),
+                            None => markup.push_str("<a>"),
+                        }
+                        push_markup(children, markup);
+                        markup.push_str("</a>");
+                    }
+                }
+            }
+        }
+        let mut markup = String::new();
+        push_markup(&parse_inlines(source), &mut markup);
+        markup
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            markup("see [Embark](https://embark.dev)"),
+            r#"see <a href="https://embark.dev">Embark</a>"#
+        );
+        assert_eq!(
+            markup(r#"[*kajiya*](<https://github.com/EmbarkStudios/kajiya> "title")"#),
+            r#"<a href="https://github.com/EmbarkStudios/kajiya"><em>kajiya</em></a>"#
+        );
+        assert_eq!(
+            markup("[wiki](https://en.wikipedia.org/wiki/Rust_(language))"),
+            r#"<a href="https://en.wikipedia.org/wiki/Rust_(language)">wiki</a>"#
+        );
+        assert_eq!(markup("[not a link] (url)"), "[not a link] (url)");
+        assert_eq!(markup("[unclosed](url"), "[unclosed](url");
+    }
+
+    #[test]
+    fn unsafe_link_schemes_lose_their_href() {
+        let unsafe_links = [
+            "[x](javascript:alert(1))",
+            "[x](JavaScript:alert(1))",
+            "[x](&#106;avascript:alert(1))",
+            "[x](java&#x09;script:alert(1))",
+            "[x](data:text/html;base64,PHNjcmlwdD4=)",
+            "[x](vbscript:msgbox(1))",
+            r#"<a href="javascript:alert(1)">x</a>"#,
+            "<a href=javascript:alert(1)>x</a>",
+        ];
+        for link in &unsafe_links {
+            assert_eq!(markup(link), "<a>x</a>", "{}", link);
+        }
+    }
+
+    #[test]
+    fn inline_html() {
+        assert_eq!(
+            markup(r#"<b onclick="alert(1)">bold</b> <code>&lt;T&gt;</code>"#),
+            "<strong>bold</strong> <code>&lt;T></code>"
+        );
+        assert_eq!(markup("a<script>alert(1)</script>b"), "ab");
+        assert_eq!(markup("a<!-- comment -->b"), "ab");
+    }
+
+    #[test]
+    fn disallowed_inline_html_is_text() {
+        assert_eq!(
+            markup(r#"<div class="x">text</div>"#),
+            r#"&lt;div class="x">text&lt;/div>"#
+        );
+        assert_eq!(
+            markup(r#"<img src=x onerror="alert(1)">"#),
+            r#"&lt;img src=x onerror="alert(1)">"#
+        );
+        assert_eq!(markup("<em>unclosed"), "&lt;em>unclosed");
+        assert_eq!(markup("1 < 2 and Vec<u8>"), "1 &lt; 2 and Vec&lt;u8>");
+        assert_eq!(markup("&lt;script&gt;"), "&lt;script>");
+    }
+
+    #[test]
+    fn emphasis() {
+        assert_eq!(
+            markup("*em* **strong** _em_ __strong__"),
+            "<em>em</em> <strong>strong</strong> <em>em</em> <strong>strong</strong>"
+        );
+        assert_eq!(
+            markup("**strong *and em* text**"),
+            "<strong>strong <em>and em</em> text</strong>"
+        );
+        assert_eq!(markup("snake_case_name"), "snake_case_name");
+    }
+
+    #[test]
+    fn unclosed_emphasis_is_text() {
+        assert_eq!(markup("*unclosed"), "*unclosed");
+        assert_eq!(markup("**unclosed"), "**unclosed");
+        assert_eq!(markup("* not emphasis *"), "* not emphasis *");
+        assert_eq!(markup("`unclosed code"), "`unclosed code");
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(markup(r"\*not em\*"), "*not em*");
+        assert_eq!(markup(r"\[not\](a link)"), "[not](a link)");
+        assert_eq!(markup(r"\<b>not bold"), "&lt;b>not bold");
+        assert_eq!(markup(r"C:\path"), r"C:\path");
+    }
+
+    #[test]
+    fn code_spans() {
+        assert_eq!(markup("`*raw*`"), "<code>*raw*</code>");
+        assert_eq!(markup("`` a ` b ``"), "<code>a ` b</code>");
+    }
+
+    #[test]
+    fn blocks() {
+        let blocks = parse_blocks("para\ngraph\n\n- a\n  continued\n- b\n\n1. one\n2. two");
+        assert_eq!(blocks.len(), 3);
+        assert!(matches!(&blocks[0], Block::Paragraph(text) if text == "para graph"));
+        assert!(matches!(
+            &blocks[1],
+            Block::UnorderedList(items) if items == &["a continued", "b"]
+        ));
+        assert!(matches!(&blocks[2], Block::OrderedList(items) if items == &["one", "two"]));
+    }
+
+    #[test]
+    fn plain_text_of_blocks() {
+        assert_eq!(
+            plain_text("A [link](https://embark.dev) and *em*.\n\n- one\n- `two`"),
+            "A link and em. one two"
+        );
+    }
+}
@@ -1,3 +1,9 @@
 pub mod home;
+pub mod newsletter;
+pub mod not_found;
 pub mod partial;
+pub mod project;
+pub mod search;
+pub mod sponsorships;
+pub mod tag_index;
 pub mod tags;
@@ -0,0 +1,45 @@
+use super::partial::{view_newsletter_edition, view_section_back};
+use crate::{date::Date, Data, Msg};
+use seed::{prelude::*, *};
+use std::collections::BTreeMap;
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(data: &Data, loading: bool, base_url: &Url) -> Vec<Node<Msg>> {
+    vec![
+        view_section_back(base_url),
+        view_section_archive(data, loading),
+    ]
+}
+
+fn view_section_archive(data: &Data, loading: bool) -> Node<Msg> {
+    let mut editions_by_year = BTreeMap::<_, Vec<_>>::new();
+    for edition in data.newsletter_editions() {
+        editions_by_year
+            .entry(edition.date.map(Date::year))
+            .or_default()
+            .push(edition);
+    }
+
+    section![
+        id!("newsletter-archive"),
+        div![
+            C!["container"],
+            h1!["Newsletter archive"],
+            IF!(loading => p!["Loading editions..."]),
+            IF!(!loading && editions_by_year.is_empty() => p!["There are no editions yet."]),
+            // Editions without a date are listed after the oldest year.
+            editions_by_year.into_iter().rev().map(|(year, editions)| {
+                div![
+                    h2![year.map_or_else(|| "Undated".to_owned(), |year| year.to_string())],
+                    ul![
+                        C!["newsletter-editions"],
+                        editions.into_iter().map(view_newsletter_edition)
+                    ]
+                ]
+            })
+        ]
+    ]
+}
@@ -0,0 +1,81 @@
+use super::partial::view_section_back;
+use crate::{href::Href, suggest, tag_counts, Data, Msg, ProjectSort, TagMatch, Urls};
+use seed::{prelude::*, *};
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(requested_url: &str, data: &Data, loading: bool, base_url: &Url) -> Vec<Node<Msg>> {
+    vec![
+        view_section_back(base_url),
+        section![
+            id!("not-found"),
+            div![
+                C!["container"],
+                h1!["Page not found"],
+                p!["There is nothing at ", code![requested_url], "."],
+                if loading {
+                    empty![]
+                } else {
+                    view_suggestion(requested_url, data, base_url)
+                },
+                p![
+                    "Try the ",
+                    a![
+                        attrs! {At::Href => Urls::new(base_url).home().href()},
+                        "project list"
+                    ],
+                    " or browse ",
+                    a![
+                        attrs! {At::Href => Urls::new(base_url).tag_index().href()},
+                        "all tags"
+                    ],
+                    "."
+                ]
+            ]
+        ],
+    ]
+}
+
+/// Suggests the tag or project whose name is closest to the last segment of the path.
+fn view_suggestion(requested_url: &str, data: &Data, base_url: &Url) -> Node<Msg> {
+    let path = requested_url
+        .split(|c: char| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let query = path
+        .rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or_default();
+
+    let tags = tag_counts(&data.projects);
+    let candidates = data
+        .projects
+        .iter()
+        .map(|project| project.name.as_str())
+        .chain(tags.iter().map(|(tag, _)| *tag));
+
+    let suggestion = match suggest::closest(query, candidates) {
+        Some(suggestion) => suggestion,
+        None => return empty![],
+    };
+    let (url, label) = if data
+        .projects
+        .iter()
+        .any(|project| project.name == suggestion)
+    {
+        (Urls::new(base_url).project(suggestion), suggestion)
+    } else {
+        (
+            Urls::new(base_url).tags(&[suggestion], TagMatch::Any, ProjectSort::default()),
+            data.tag_label(suggestion),
+        )
+    };
+    p![
+        C!["suggestion"],
+        "Did you mean ",
+        a![attrs! {At::Href => url.href()}, label],
+        "?"
+    ]
+}
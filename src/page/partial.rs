@@ -1,71 +1,358 @@
-use crate::{Msg, Project, Urls};
+use crate::{
+    href::Href,
+    markdown::view_markdown,
+    query, sanitize,
+    search::{SearchIndex, SearchResult},
+    validation::{is_valid_color, is_valid_image_url},
+    Data, Msg, NewsletterEdition, Project, ProjectSort, SiteConfig, Sponsorship,
+    SponsorshipPlatform, TagInfo, TagMatch, Urls,
+};
 use seed::{prelude::*, *};
+use std::collections::BTreeMap;
+use std::ops::Range;
 
-pub fn repo_url(project_name: &str) -> String {
-    format!("https://github.com/EmbarkStudios/{}", project_name)
+/// Placeholder cards of a project listing while project data are loading.
+pub const SKELETON_PROJECT_COUNT: usize = 3;
+
+pub fn repo_url(site: &SiteConfig, project_name: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        site.forge_url.trim_end_matches('/'),
+        site.org_name,
+        project_name
+    )
 }
 
-pub fn star_button_src(project_name: &str) -> String {
-    format!("https://ghbtns.com/github-btn.html?user=EmbarkStudios&repo={}&type=star&count=true&size=large", project_name)
+pub fn star_button_src(site: &SiteConfig, project_name: &str) -> String {
+    format!(
+        "https://ghbtns.com/github-btn.html?user={}&repo={}&type=star&count=true&size=large",
+        site.org_name, project_name
+    )
 }
 
 pub fn view_category<'a>(
     tag: &str,
     projects: impl Iterator<Item = &'a Project>,
+    loading: bool,
+    data: &Data,
     base_url: &Url,
 ) -> Node<Msg> {
+    let tag_info = data.tag_info(tag);
+
     section![
         C!["category"],
         h2![
             C!["category-title"],
             "Our ",
-            span![C!["category-tag"], tag],
+            span![
+                C!["category-tag"],
+                tag_info.and_then(view_tag_icon),
+                data.tag_label(tag)
+            ],
             " projects"
         ],
+        tag_info
+            .and_then(|tag_info| tag_info.description.as_ref())
+            .map(|description| p![C!["category-description"], description]),
         div![
             id!(tag),
             C!["projects-container"],
-            projects.map(|project| view_project(project, base_url))
+            if loading {
+                view_skeleton_projects(SKELETON_PROJECT_COUNT, "project")
+            } else {
+                projects
+                    .map(|project| view_project(project, data, base_url))
+                    .collect()
+            }
         ]
     ]
 }
 
-pub fn view_project(project: &Project, base_url: &Url) -> Node<Msg> {
-    a![
+pub fn view_section_back(base_url: &Url) -> Node<Msg> {
+    section![
+        C!["full-width-section cover"],
+        div![
+            C!["container"],
+            a![
+                attrs! {At::Href => Urls::new(base_url).home().href()},
+                h1![i![C!["fa", "fa-long-arrow-left"]], "Back",]
+            ]
+        ]
+    ]
+}
+
+/// Placeholder cards displayed while project data are loading.
+pub fn view_skeleton_projects(count: usize, class: &str) -> Vec<Node<Msg>> {
+    (0..count)
+        .map(|_| {
+            div![
+                C![class, "project-skeleton"],
+                div![C!["skeleton-line", "skeleton-line--title"]],
+                div![C!["skeleton-line"]],
+                div![C!["skeleton-line", "skeleton-line--short"]],
+            ]
+        })
+        .collect()
+}
+
+pub fn view_project(project: &Project, data: &Data, base_url: &Url) -> Node<Msg> {
+    div![
         C!["project"],
-        attrs! {At::Href => repo_url(&project.name)},
         div![
             h3![
                 C!["title"],
                 span![C!["emoji"], &project.emoji],
                 " ",
-                &project.name,
+                view_project_link(project, base_url, &project.name),
+                view_repo_link(&data.site, &project.name),
             ],
-            p![raw![&project.description],],
-            view_tags(project.tags.iter(), base_url)
+            view_markdown(&project.description),
+            view_tags(project.tags.iter(), data, base_url)
         ],
-        view_star_button(&project.name),
+        IF!(data.site.is_github() => view_star_button(&data.site, &project.name)),
     ]
 }
 
-pub fn view_tags<'a>(tags: impl Iterator<Item = &'a String>, base_url: &Url) -> Node<Msg> {
+/// Project card with the parts matching the search query highlighted.
+pub fn view_search_result(
+    result: &SearchResult,
+    search_index: &SearchIndex,
+    selected: bool,
+    data: &Data,
+    base_url: &Url,
+) -> Node<Msg> {
+    let project = match data.projects.get(result.project) {
+        Some(project) => project,
+        None => return empty![],
+    };
+    div![
+        C![
+            "project",
+            "search-result",
+            IF!(selected => "search-result--selected")
+        ],
+        div![
+            h3![
+                C!["title"],
+                span![C!["emoji"], &project.emoji],
+                " ",
+                view_project_link(
+                    project,
+                    base_url,
+                    view_highlighted(&project.name, &result.name_ranges)
+                ),
+            ],
+            p![view_highlighted(
+                search_index.description(result.project),
+                &result.description_ranges
+            )],
+            view_tags(project.tags.iter(), data, base_url)
+        ],
+    ]
+}
+
+/// Inline feedback for the tokens of the search `query` that can't be parsed.
+pub fn view_query_errors(query: &str) -> Node<Msg> {
+    let errors = query::parse(query).errors;
+    if errors.is_empty() {
+        return empty![];
+    }
+    ul![
+        C!["query-errors"],
+        errors
+            .iter()
+            .map(|error| li![code![&error.token], ": ", error.kind.to_string()])
+    ]
+}
+
+/// Link to the project's detail page stretched over the whole card by `.project-link` in
+/// `style.css`. Cards aren't links themselves, because the links inside them can't be nested.
+pub fn view_project_link(
+    project: &Project,
+    base_url: &Url,
+    content: impl UpdateEl<Msg>,
+) -> Node<Msg> {
+    a![
+        C!["project-link"],
+        attrs! {At::Href => Urls::new(base_url).project(&project.name).href()},
+        content
+    ]
+}
+
+/// Secondary link to the project's repository, displayed inside cards that lead to the detail page.
+pub fn view_repo_link(site: &SiteConfig, project_name: &str) -> Node<Msg> {
+    a![
+        C!["fa", "fa-github", "repo-link"],
+        attrs! {
+            At::Href => repo_url(site, project_name),
+            At::Title => "Source code",
+        },
+    ]
+}
+
+pub fn view_tags<'a>(
+    tags: impl Iterator<Item = &'a String>,
+    data: &Data,
+    base_url: &Url,
+) -> Node<Msg> {
     div![
         C!["tags"],
         tags.map(|tag| {
+            let url = Urls::new(base_url).tags(&[tag], TagMatch::Any, ProjectSort::default());
+            view_tag(
+                tag,
+                data,
+                a![
+                    attrs! {At::Href => url.href()},
+                    data.tag_info(tag).and_then(view_tag_icon),
+                    data.tag_label(tag)
+                ],
+            )
+        })
+    ]
+}
+
+/// Links switching the order of a project listing; `sort_url` links to the listing in an order.
+pub fn view_sort_controls(
+    current_sort: ProjectSort,
+    data: &Data,
+    sort_url: impl Fn(ProjectSort) -> Url,
+) -> Node<Msg> {
+    let stars_known = data.projects.iter().any(|project| project.stars.is_some());
+    div![
+        C!["sort-controls"],
+        "Sort by ",
+        ProjectSort::ALL
+            .iter()
+            .copied()
+            .filter(|sort| *sort != ProjectSort::Stars || stars_known)
+            .map(|sort| {
+                a![
+                    C![IF!(sort == current_sort => "active")],
+                    attrs! {At::Href => sort_url(sort).href()},
+                    sort.label(),
+                ]
+            }),
+    ]
+}
+
+pub fn view_newsletter_edition(edition: &NewsletterEdition) -> Node<Msg> {
+    li![
+        C!["newsletter-edition", "campaign"],
+        edition.date.map(|date| {
+            vec![
+                span![C!["newsletter-edition__date"], date.to_string()],
+                Node::new_text(" - "),
+            ]
+        }),
+        a![
+            IF!(sanitize::is_safe_url(&edition.url) => attrs! {
+                At::Href => &edition.url,
+                At::Title => &edition.title,
+                At::Target => "_blank",
+                At::Rel => "noopener noreferrer",
+            }),
+            &edition.title
+        ],
+    ]
+}
+
+/// Sponsorship cards under a heading for every platform.
+pub fn view_sponsorships(
+    sponsorships_by_platform: BTreeMap<SponsorshipPlatform, Vec<&Sponsorship>>,
+) -> Vec<Node<Msg>> {
+    sponsorships_by_platform
+        .into_iter()
+        .map(|(platform, sponsorships)| {
             div![
-                C!["tag", format!("tag-{}", tag)],
-                a![attrs! {At::Href => Urls::new(base_url).tags(tag)}, tag]
+                C!["sponsorship-platform"],
+                h3![platform.label()],
+                div![
+                    C!["sponsorships"],
+                    sponsorships.into_iter().map(view_sponsorship)
+                ]
             ]
         })
+        .collect()
+}
+
+fn view_sponsorship(sponsorship: &Sponsorship) -> Node<Msg> {
+    a![
+        C!["sponsorship"],
+        IF!(sanitize::is_safe_url(&sponsorship.url) => attrs! {At::Href => &sponsorship.url}),
+        sponsorship
+            .avatar
+            .as_ref()
+            .filter(|avatar| is_valid_image_url(avatar))
+            .map(|avatar| {
+                img![
+                    C!["sponsorship__avatar"],
+                    attrs! {At::Src => avatar, At::Alt => ""}
+                ]
+            }),
+        div![
+            strong![C!["sponsorship__recipient"], &sponsorship.recipient],
+            div![C!["sponsorship__category"], &sponsorship.category],
+            sponsorship.start_date.map(|start_date| {
+                div![C!["sponsorship__since"], format!("Since {}", start_date)]
+            }),
+        ]
     ]
 }
 
-pub fn view_star_button(project_name: &str) -> Node<Msg> {
+/// Tag pill colored and described according to the tag's metadata.
+pub fn view_tag(tag: &str, data: &Data, content: Node<Msg>) -> Node<Msg> {
+    let tag_info = data.tag_info(tag);
+    div![
+        C!["tag"],
+        tag_info
+            .and_then(|tag_info| tag_info.color.as_ref())
+            .filter(|color| is_valid_color(color))
+            .map(|color| style! {St::Background => color}),
+        tag_info
+            .and_then(|tag_info| tag_info.description.as_ref())
+            .map(|description| attrs! {At::Title => description}),
+        content
+    ]
+}
+
+/// `text` with the byte `ranges` wrapped in `<mark>`; ranges may overlap.
+pub fn view_highlighted(text: &str, ranges: &[Range<usize>]) -> Vec<Node<Msg>> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|range| range.start);
+
+    let mut nodes = Vec::new();
+    let mut position = 0;
+    for range in ranges {
+        let start = range.start.max(position);
+        if start >= range.end {
+            continue;
+        }
+        if start > position {
+            nodes.push(Node::new_text(text[position..start].to_owned()));
+        }
+        nodes.push(mark![&text[start..range.end]]);
+        position = range.end;
+    }
+    if position < text.len() {
+        nodes.push(Node::new_text(text[position..].to_owned()));
+    }
+    nodes
+}
+
+pub fn view_tag_icon(tag_info: &TagInfo) -> Option<Node<Msg>> {
+    tag_info
+        .icon
+        .as_ref()
+        .map(|icon| i![C!["fa", format!("fa-{}", icon), "tag-icon"]])
+}
+
+pub fn view_star_button(site: &SiteConfig, project_name: &str) -> Node<Msg> {
     iframe![
         C!["star-button"],
         style! {St::Border => 0},
         attrs! {
-            At::Src => star_button_src(project_name),
+            At::Src => star_button_src(site, project_name),
             At::Width => px(160),
             At::Height => px(30),
         }
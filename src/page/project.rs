@@ -0,0 +1,161 @@
+use super::partial::{
+    repo_url, view_project, view_section_back, view_skeleton_projects, view_star_button, view_tags,
+};
+use crate::{
+    href::Href, markdown::view_markdown, sanitize, sort_projects, suggest,
+    validation::is_valid_image_url, Data, Msg, Project, ProjectSort, Urls,
+};
+use seed::{prelude::*, *};
+
+const RELATED_PROJECT_COUNT: usize = 3;
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(name: &str, data: &Data, loading: bool, base_url: &Url) -> Vec<Node<Msg>> {
+    let project = data.projects.iter().find(|project| project.name == name);
+
+    vec![
+        view_section_back(base_url),
+        match project {
+            Some(project) => view_section_detail(project, data, base_url),
+            None if loading => view_section_loading(),
+            None => view_section_not_found(name, data, base_url),
+        },
+        project.map_or_else(
+            || empty![],
+            |project| view_section_related(project, data, base_url),
+        ),
+    ]
+}
+
+fn view_section_detail(project: &Project, data: &Data, base_url: &Url) -> Node<Msg> {
+    section![
+        id!("project"),
+        C!["project-detail"],
+        project
+            .feature_image
+            .as_ref()
+            .filter(|feature_image| is_valid_image_url(feature_image))
+            .map(|feature_image| {
+                div![
+                    C!["project-detail__image"],
+                    style! {St::BackgroundImage => format!("url({})", feature_image)},
+                ]
+            }),
+        div![
+            C!["container"],
+            h1![span![C!["emoji"], &project.emoji], &project.name],
+            view_markdown(&project.description),
+            project
+                .extended_description
+                .as_ref()
+                .map(|extended_description| view_markdown(extended_description)),
+            view_tags(project.tags.iter(), data, base_url),
+            div![
+                C!["project-detail__actions"],
+                a![
+                    C!["button-primary"],
+                    attrs! {At::Href => repo_url(&data.site, &project.name)},
+                    i![C!["fa", "fa-github"]],
+                    " Source code",
+                ],
+                IF!(data.site.is_github() => view_star_button(&data.site, &project.name)),
+            ],
+            view_links(project),
+        ]
+    ]
+}
+
+/// Links with unsafe URLs are left out; validation reports them.
+fn view_links(project: &Project) -> Node<Msg> {
+    let links = project
+        .links
+        .iter()
+        .filter(|link| sanitize::is_safe_url(&link.url))
+        .collect::<Vec<_>>();
+    if links.is_empty() {
+        return empty![];
+    }
+    ul![
+        C!["project-detail__links"],
+        links
+            .into_iter()
+            .map(|link| li![a![attrs! {At::Href => &link.url}, &link.title]])
+    ]
+}
+
+fn view_section_loading() -> Node<Msg> {
+    section![div![
+        C!["container", "projects-container"],
+        view_skeleton_projects(1, "project-featured")
+    ]]
+}
+
+fn view_section_not_found(name: &str, data: &Data, base_url: &Url) -> Node<Msg> {
+    let suggestion = suggest::closest(
+        name,
+        data.projects.iter().map(|project| project.name.as_str()),
+    );
+
+    section![div![
+        C!["container"],
+        h2!["Project not found"],
+        p![
+            "We don't have a project called ",
+            b![name],
+            ". Try the ",
+            a![
+                attrs! {At::Href => Urls::new(base_url).home().href()},
+                "project list"
+            ],
+            " instead."
+        ],
+        suggestion.map(|suggestion| {
+            p![
+                C!["suggestion"],
+                "Did you mean ",
+                a![
+                    attrs! {At::Href => Urls::new(base_url).project(suggestion).href()},
+                    suggestion
+                ],
+                "?"
+            ]
+        })
+    ]]
+}
+
+/// Projects sharing the most tags with `project`.
+fn view_section_related(project: &Project, data: &Data, base_url: &Url) -> Node<Msg> {
+    let mut related = sort_projects(&data.projects, ProjectSort::Name)
+        .into_iter()
+        .filter(|other| other.name != project.name)
+        .map(|other| {
+            let shared_tags = other
+                .tags
+                .iter()
+                .filter(|tag| project.tags.contains(tag))
+                .count();
+            (shared_tags, other)
+        })
+        .filter(|(shared_tags, _)| *shared_tags > 0)
+        .collect::<Vec<_>>();
+    // Stable sort keeps the alphabetical order for projects with the same score.
+    related.sort_by(|(shared_a, _), (shared_b, _)| shared_b.cmp(shared_a));
+
+    if related.is_empty() {
+        return empty![];
+    }
+    section![div![
+        C!["container"],
+        h2!["Related projects"],
+        div![
+            C!["projects-container"],
+            related
+                .into_iter()
+                .take(RELATED_PROJECT_COUNT)
+                .map(|(_, other)| view_project(other, data, base_url))
+        ]
+    ]]
+}
@@ -0,0 +1,103 @@
+use super::partial::{
+    view_query_errors, view_search_result, view_section_back, view_skeleton_projects,
+    SKELETON_PROJECT_COUNT,
+};
+use crate::{
+    search::{SearchIndex, SearchResult},
+    Data, Msg,
+};
+use seed::{prelude::*, *};
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(
+    query: &str,
+    results: &[SearchResult],
+    search_index: &SearchIndex,
+    data: &Data,
+    loading: bool,
+    base_url: &Url,
+) -> Vec<Node<Msg>> {
+    vec![
+        view_section_back(base_url),
+        view_section_header(query),
+        view_section_results(results, search_index, data, loading, base_url),
+    ]
+}
+
+fn view_section_header(query: &str) -> Node<Msg> {
+    section![
+        C!["search-header"],
+        div![
+            C!["container"],
+            h1!["Search"],
+            input![
+                C!["search-header__input"],
+                attrs! {
+                    At::Type => "search",
+                    At::Placeholder => "Search projects by name, tag or description",
+                    At::Value => query,
+                },
+                input_ev(Ev::Input, Msg::SearchQueryChanged),
+            ],
+            view_query_errors(query),
+            p![
+                C!["search-header__hint"],
+                "Narrow the results down with ",
+                code!["tag:rust"],
+                ", ",
+                code!["is:featured"],
+                ", ",
+                code!["name:cargo"],
+                " or ",
+                code!["description:vulkan"],
+                "; prefix a filter or a word with ",
+                code!["-"],
+                " to exclude it. Quote phrases, e.g. ",
+                code!["\"texture synthesis\""],
+                "."
+            ],
+        ]
+    ]
+}
+
+fn view_section_results(
+    results: &[SearchResult],
+    search_index: &SearchIndex,
+    data: &Data,
+    loading: bool,
+    base_url: &Url,
+) -> Node<Msg> {
+    if loading {
+        return section![div![
+            C!["container", "projects-container"],
+            view_skeleton_projects(SKELETON_PROJECT_COUNT, "project")
+        ]];
+    }
+    section![
+        id!("search-results"),
+        div![
+            C!["container"],
+            p![
+                C!["search-results__count"],
+                match results.len() {
+                    0 => "No projects match your search.".to_owned(),
+                    1 => "1 project".to_owned(),
+                    count => format!("{} projects", count),
+                }
+            ],
+            div![
+                C!["projects-container"],
+                results.iter().map(|result| view_search_result(
+                    result,
+                    search_index,
+                    false,
+                    data,
+                    base_url
+                ))
+            ]
+        ]
+    ]
+}
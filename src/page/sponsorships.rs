@@ -0,0 +1,100 @@
+use super::partial::{view_section_back, view_sponsorships};
+use crate::{href::Href, suggest, Data, Msg, SponsorshipPlatform, Urls};
+use seed::{prelude::*, *};
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(
+    platform: Option<SponsorshipPlatform>,
+    unknown_platform: Option<&str>,
+    data: &Data,
+    loading: bool,
+    base_url: &Url,
+) -> Vec<Node<Msg>> {
+    vec![
+        view_section_back(base_url),
+        unknown_platform.map_or(empty![], |unknown_platform| {
+            view_section_unknown_platform(unknown_platform, base_url)
+        }),
+        view_section_sponsorships(platform, data, loading, base_url),
+    ]
+}
+
+/// Explains that all sponsorships are displayed instead and suggests a similar platform.
+fn view_section_unknown_platform(unknown_platform: &str, base_url: &Url) -> Node<Msg> {
+    let suggestion = suggest::closest(
+        unknown_platform,
+        SponsorshipPlatform::ALL
+            .iter()
+            .map(|platform| platform.as_str()),
+    )
+    .and_then(SponsorshipPlatform::parse);
+
+    section![
+        C!["unknown-platform"],
+        div![
+            C!["container"],
+            p![
+                "There is no sponsorship platform ",
+                b![unknown_platform],
+                ", so all sponsorships are shown.",
+                suggestion.map(|suggestion| {
+                    let url = Urls::new(base_url).sponsorships(Some(suggestion));
+                    span![
+                        " Did you mean ",
+                        a![attrs! {At::Href => url.href()}, suggestion.label()],
+                        "?"
+                    ]
+                })
+            ]
+        ]
+    ]
+}
+
+fn view_section_sponsorships(
+    platform: Option<SponsorshipPlatform>,
+    data: &Data,
+    loading: bool,
+    base_url: &Url,
+) -> Node<Msg> {
+    let sponsorships = data.sponsorships_by_platform(platform);
+    // Only platforms with at least one sponsorship are offered as filters.
+    let platforms = data
+        .sponsorships_by_platform(None)
+        .keys()
+        .copied()
+        .collect::<Vec<_>>();
+
+    section![
+        id!("sponsorships"),
+        div![
+            C!["container"],
+            h1!["Sponsorships"],
+            p!["Individuals and projects we currently support."],
+            div![
+                C!["sponsorship-filter"],
+                "Platform ",
+                a![
+                    C![IF!(platform.is_none() => "active")],
+                    attrs! {At::Href => Urls::new(base_url).sponsorships(None).href()},
+                    "all",
+                ],
+                platforms.into_iter().map(|filter_platform| {
+                    let url = Urls::new(base_url).sponsorships(Some(filter_platform));
+                    a![
+                        C![IF!(platform == Some(filter_platform) => "active")],
+                        attrs! {At::Href => url.href()},
+                        filter_platform.label(),
+                    ]
+                }),
+            ],
+            IF!(loading => p!["Loading sponsorships..."]),
+            IF!(!loading && sponsorships.is_empty() => {
+                p![C!["sponsorship-filter__empty"], "There are no sponsorships to show."]
+            }),
+            view_sponsorships(sponsorships),
+        ]
+    ]
+}
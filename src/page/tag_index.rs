@@ -0,0 +1,54 @@
+use super::partial::{view_section_back, view_tag_icon};
+use crate::{href::Href, tag_counts, Data, Msg, ProjectSort, TagMatch, Urls};
+use seed::{prelude::*, *};
+
+// ------ ------
+//     View
+// ------ ------
+
+pub fn view(data: &Data, loading: bool, base_url: &Url) -> Vec<Node<Msg>> {
+    vec![
+        view_section_back(base_url),
+        view_section_tags(data, loading, base_url),
+    ]
+}
+
+fn view_section_tags(data: &Data, loading: bool, base_url: &Url) -> Node<Msg> {
+    section![
+        id!("tags"),
+        div![
+            C!["container"],
+            h1!["All tags"],
+            IF!(loading => p!["Loading tags..."]),
+            ul![
+                C!["tag-index"],
+                tag_counts(&data.projects).into_iter().map(|(tag, count)| {
+                    let tag_info = data.tag_info(tag);
+                    let url =
+                        Urls::new(base_url).tags(&[tag], TagMatch::Any, ProjectSort::default());
+                    li![a![
+                        attrs! {At::Href => url.href()},
+                        span![
+                            C!["tag-index__label"],
+                            tag_info.and_then(view_tag_icon),
+                            data.tag_label(tag),
+                        ],
+                        tag_info
+                            .and_then(|tag_info| tag_info.description.as_ref())
+                            .map(|description| {
+                                span![C!["tag-index__description"], description]
+                            }),
+                        span![
+                            C!["tag-index__count"],
+                            if count == 1 {
+                                "1 project".to_owned()
+                            } else {
+                                format!("{} projects", count)
+                            }
+                        ],
+                    ]]
+                })
+            ]
+        ]
+    ]
+}
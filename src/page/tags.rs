@@ -1,37 +1,246 @@
-use super::partial::view_category;
-use crate::{iter_projects_by_tag, Msg, Project, Urls};
+use super::partial::{
+    view_project, view_section_back, view_skeleton_projects, view_sort_controls, view_tag,
+    view_tag_icon, SKELETON_PROJECT_COUNT,
+};
+use crate::{
+    href::Href, iter_projects_by_tag, iter_projects_by_tags, sort_projects, suggest, tag_counts,
+    Data, Msg, Project, ProjectSort, Tag, TagMatch, Urls,
+};
 use seed::{prelude::*, *};
+use std::collections::BTreeSet;
 
 // ------ ------
 //     View
 // ------ ------
 
-pub fn view(tag: &str, projects: &[Project], base_url: &Url) -> Vec<Node<Msg>> {
+pub fn view(
+    tags: &BTreeSet<Tag>,
+    match_mode: TagMatch,
+    sort: ProjectSort,
+    data: &Data,
+    loading: bool,
+    base_url: &Url,
+) -> Vec<Node<Msg>> {
+    let projects = sort_projects(
+        iter_projects_by_tags(&data.projects, tags, match_mode),
+        sort,
+    );
+
     vec![
         view_section_back(base_url),
-        view_section_projects(tag, projects, base_url),
+        view_section_header(tags, match_mode, data),
+        view_section_filter(tags, match_mode, sort, data, base_url),
+        if loading {
+            empty![]
+        } else {
+            view_section_unknown_tags(tags, match_mode, sort, data, base_url)
+        },
+        view_section_projects(projects, loading, data, base_url),
+    ]
+}
+
+fn view_section_header(tags: &BTreeSet<Tag>, match_mode: TagMatch, data: &Data) -> Node<Msg> {
+    let separator = match match_mode {
+        TagMatch::Any => " or ",
+        TagMatch::All => " and ",
+    };
+    let mut title = Vec::new();
+    for (index, tag) in tags.iter().enumerate() {
+        if index > 0 {
+            title.push(Node::new_text(separator));
+        }
+        title.extend(data.tag_info(tag).and_then(view_tag_icon));
+        title.push(Node::new_text(format!(" {}", data.tag_label(tag))));
+    }
+    // The description is only meaningful when a single tag is selected.
+    let description = match tags.iter().collect::<Vec<_>>().as_slice() {
+        [tag] => data
+            .tag_info(tag)
+            .and_then(|tag_info| tag_info.description.as_ref()),
+        _ => None,
+    };
+
+    section![
+        C!["tag-header"],
+        div![
+            C!["container"],
+            h1![title],
+            description.map(|description| p![description]),
+        ]
     ]
 }
 
-fn view_section_back(base_url: &Url) -> Node<Msg> {
+fn view_section_filter(
+    tags: &BTreeSet<Tag>,
+    match_mode: TagMatch,
+    sort: ProjectSort,
+    data: &Data,
+    base_url: &Url,
+) -> Node<Msg> {
+    let other_tags = tag_counts(&data.projects)
+        .into_iter()
+        .map(|(tag, _)| tag)
+        .filter(|tag| !tags.contains(*tag))
+        .collect::<Vec<_>>();
+
     section![
-        C!["full-width-section cover"],
+        C!["tag-filter"],
         div![
             C!["container"],
-            a![
-                attrs! {At::Href => Urls::new(base_url).home()},
-                h1![i![C!["fa", "fa-long-arrow-left"]], "Back",]
-            ]
+            div![
+                C!["tags", "tag-filter__selected"],
+                tags.iter().map(|tag| {
+                    let remaining = tags
+                        .iter()
+                        .filter(|other| *other != tag)
+                        .collect::<Vec<_>>();
+                    let remove_url = if remaining.is_empty() {
+                        Urls::new(base_url).tag_index()
+                    } else {
+                        Urls::new(base_url).tags(&remaining, match_mode, sort)
+                    };
+                    view_tag(
+                        tag,
+                        data,
+                        a![
+                            attrs! {
+                                At::Href => remove_url.href(),
+                                At::Title => format!("Remove {}", data.tag_label(tag)),
+                            },
+                            data.tag_label(tag),
+                            " ",
+                            i![C!["fa", "fa-times"]],
+                        ],
+                    )
+                }),
+            ],
+            IF!(tags.len() > 1 => {
+                let tags = tags.iter().collect::<Vec<_>>();
+                div![
+                    C!["tag-filter__match"],
+                    "Match ",
+                    TagMatch::ALL.iter().map(|mode| {
+                        let url = Urls::new(base_url).tags(&tags, *mode, sort);
+                        a![
+                            C![IF!(*mode == match_mode => "active")],
+                            attrs! {At::Href => url.href()},
+                            mode.as_str(),
+                        ]
+                    }),
+                    " of the selected tags",
+                ]
+            }),
+            view_sort_controls(sort, data, |sort| {
+                let tags = tags.iter().collect::<Vec<_>>();
+                Urls::new(base_url).tags(&tags, match_mode, sort)
+            }),
+            IF!(!other_tags.is_empty() => div![
+                C!["tags", "tag-filter__add"],
+                "Add a tag: ",
+                other_tags.into_iter().map(|tag| {
+                    let mut selected = tags.iter().map(String::as_str).collect::<Vec<_>>();
+                    selected.push(tag);
+                    let url = Urls::new(base_url).tags(&selected, match_mode, sort);
+                    view_tag(
+                        tag,
+                        data,
+                        a![
+                            attrs! {At::Href => url.href()},
+                            i![C!["fa", "fa-plus"]],
+                            " ",
+                            data.tag_label(tag),
+                        ],
+                    )
+                }),
+            ]),
         ]
     ]
 }
 
-fn view_section_projects(tag: &str, projects: &[Project], base_url: &Url) -> Node<Msg> {
+/// Explains why selected tags that no project uses yield nothing and suggests similar tags.
+fn view_section_unknown_tags(
+    tags: &BTreeSet<Tag>,
+    match_mode: TagMatch,
+    sort: ProjectSort,
+    data: &Data,
+    base_url: &Url,
+) -> Node<Msg> {
+    let unknown_tags = tags
+        .iter()
+        .filter(|tag| iter_projects_by_tag(&data.projects, tag).next().is_none())
+        .collect::<Vec<_>>();
+    if unknown_tags.is_empty() {
+        return empty![];
+    }
+    let known_tags = tag_counts(&data.projects);
+
+    section![
+        C!["unknown-tags"],
+        div![
+            C!["container"],
+            unknown_tags.into_iter().map(|unknown_tag| {
+                let suggestion = suggest::closest(
+                    unknown_tag,
+                    known_tags
+                        .iter()
+                        .map(|(tag, _)| *tag)
+                        .filter(|tag| !tags.contains(*tag)),
+                );
+                p![
+                    "There are no projects tagged ",
+                    b![unknown_tag],
+                    ".",
+                    suggestion.map(|suggestion| {
+                        let replaced = tags
+                            .iter()
+                            .map(|tag| {
+                                if tag == unknown_tag {
+                                    suggestion
+                                } else {
+                                    tag.as_str()
+                                }
+                            })
+                            .collect::<Vec<_>>();
+                        let url = Urls::new(base_url).tags(&replaced, match_mode, sort);
+                        span![
+                            " Did you mean ",
+                            a![attrs! {At::Href => url.href()}, data.tag_label(suggestion)],
+                            "?"
+                        ]
+                    })
+                ]
+            })
+        ]
+    ]
+}
+
+fn view_section_projects(
+    projects: Vec<&Project>,
+    loading: bool,
+    data: &Data,
+    base_url: &Url,
+) -> Node<Msg> {
+    let projects = if loading {
+        view_skeleton_projects(SKELETON_PROJECT_COUNT, "project")
+    } else {
+        projects
+            .into_iter()
+            .map(|project| view_project(project, data, base_url))
+            .collect()
+    };
+
     section![
         id!("projects"),
         div![
             C!["container"],
-            view_category(tag, iter_projects_by_tag(projects, tag), base_url)
+            if projects.is_empty() {
+                p![
+                    C!["tag-filter__empty"],
+                    "No projects match the selected tags."
+                ]
+            } else {
+                div![C!["projects-container"], projects]
+            }
         ]
     ]
 }
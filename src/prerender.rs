@@ -0,0 +1,352 @@
+//! Static HTML of the site's pages, written to the dist folder by `src/bin/prerender.rs`.
+//!
+//! Every page is rendered by `view` with the data loaded, so crawlers and visitors without
+//! JavaScript get the real content. The data are embedded in the page as well (see `PageData`),
+//! so the app renders the same markup when it takes the page over.
+
+use crate::{
+    rank_project_names, sanitize::push_escaped_text, search, tag_counts, update_search_results,
+    validation::is_valid_path_segment, view, Content, Data, DataStatus, Model, Msg, Page,
+    ProjectSort, TagMatch, Urls,
+};
+use seed::{
+    prelude::*,
+    virtual_dom::{AtValue, CSSValue},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The app's mount point in `index.html`; the rendered page is inserted into it.
+pub const APP_ELEMENT: &str = r#"<div id="app"></div>"#;
+/// Id of the `script` element with `PageData`. It follows the mount point, because the app
+/// replaces the mount point's content.
+const PAGE_DATA_ID: &str = "page-data";
+
+/// Elements without a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub struct Route {
+    pub url: Url,
+    /// Path of the rendered page in the dist folder.
+    pub file: String,
+    /// Whether search engines should index the page, see `sitemap`.
+    pub indexed: bool,
+}
+
+impl Route {
+    fn new(url: Url, file: impl Into<String>) -> Self {
+        Self {
+            url,
+            file: file.into(),
+            indexed: true,
+        }
+    }
+}
+
+pub struct RenderedPage {
+    /// Path in the dist folder.
+    pub file: String,
+    pub html: String,
+}
+
+/// The data a page has been rendered with; `init` starts with them instead of an empty page.
+#[derive(Serialize, Deserialize)]
+pub struct PageData {
+    pub data: Data,
+    pub content: Option<Content>,
+}
+
+/// `PageData` of the current document, if it has been rendered by `render`.
+pub fn load_page_data() -> Option<PageData> {
+    let json = seed::document()
+        .get_element_by_id(PAGE_DATA_ID)?
+        .text_content()?;
+    serde_json::from_str(&json)
+        .map_err(|error| seed::error!("invalid page data:", error))
+        .ok()
+}
+
+/// Every page derivable from `data`: home, the tag index, each tag, each project,
+/// the newsletter archive and the sponsorships, all of them and per platform.
+///
+/// Pages selected by search parameters can't be served as files, so `netlify.toml` rewrites
+/// their URLs to the files. Tags and projects whose names can't be part of a file path are
+/// skipped; `validation` reports them.
+pub fn routes(data: &Data) -> Vec<Route> {
+    let base_url = Url::new();
+
+    let mut routes = vec![
+        Route::new(Urls::new(&base_url).home(), "index.html"),
+        Route::new(Urls::new(&base_url).tag_index(), "tags/index.html"),
+    ];
+    routes.extend(
+        tag_counts(&data.projects)
+            .into_iter()
+            .filter(|(tag, _)| is_valid_path_segment(tag))
+            .map(|(tag, _)| {
+                Route::new(
+                    Urls::new(&base_url).tags(&[tag], TagMatch::Any, ProjectSort::default()),
+                    format!("tags/{}/index.html", tag),
+                )
+            }),
+    );
+    routes.extend(
+        data.projects
+            .iter()
+            .filter(|project| is_valid_path_segment(&project.name))
+            .map(|project| {
+                Route::new(
+                    Urls::new(&base_url).project(&project.name),
+                    format!("projects/{}/index.html", project.name),
+                )
+            }),
+    );
+    routes.push(Route::new(
+        Urls::new(&base_url).newsletter(),
+        "newsletter/index.html",
+    ));
+    routes.push(Route::new(
+        Urls::new(&base_url).sponsorships(None),
+        "sponsorships/index.html",
+    ));
+    routes.extend(data.sponsorships_by_platform(None).keys().map(|platform| {
+        Route::new(
+            Urls::new(&base_url).sponsorships(Some(*platform)),
+            format!("sponsorships/{}/index.html", platform.as_str()),
+        )
+    }));
+    // Search results would only duplicate the other pages.
+    routes.push(Route {
+        indexed: false,
+        ..Route::new(Urls::new(&base_url).search(""), "search/index.html")
+    });
+    routes
+}
+
+/// Renders every route of `routes` into `template`, the content of `index.html`.
+/// The template has to contain `APP_ELEMENT`.
+///
+/// # Errors
+///
+/// Returns an error if `page_data` can't be serialized into the page data element.
+pub fn render(page_data: PageData, template: &str) -> serde_json::Result<Vec<RenderedPage>> {
+    let routes = routes(&page_data.data);
+    let document = with_title(template, &page_data.data.site.title);
+    let page_data_element = page_data_element(&page_data)?;
+
+    let mut model = Model::new(Url::new(), Vec::new());
+    model.data = page_data.data;
+    rank_project_names(&mut model.data.projects);
+    model.search_index = search::SearchIndex::new(&model.data);
+    model.data_status = DataStatus::Loaded;
+    if page_data.content.is_some() {
+        model.content_status = DataStatus::Loaded;
+    }
+    model.content = page_data.content;
+
+    Ok(routes
+        .into_iter()
+        .map(|route| {
+            model.page = Page::init(route.url);
+            if let Page::Search(query) = &model.page {
+                model.search_query = query.clone();
+                update_search_results(&mut model);
+            }
+            let app = format!(
+                r#"<div id="app">{}</div>{}"#,
+                html(&view(&model)),
+                page_data_element
+            );
+            RenderedPage {
+                file: route.file,
+                html: document.replacen(APP_ELEMENT, &app, 1),
+            }
+        })
+        .collect())
+}
+
+/// `page_data` as JSON in a `script` element. `<` is escaped, so the JSON can't close the
+/// element.
+fn page_data_element(page_data: &PageData) -> serde_json::Result<String> {
+    let json = serde_json::to_string(page_data)?.replace('<', r"\u003c");
+    Ok(format!(
+        r#"<script id="{}" type="application/json">{}</script>"#,
+        PAGE_DATA_ID, json
+    ))
+}
+
+/// `template` with the content of its `<title>` element replaced, like the app does.
+fn with_title(template: &str, title: &str) -> String {
+    let start = template
+        .find("<title>")
+        .map(|index| index + "<title>".len());
+    let end = template.find("</title>");
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => {
+            let mut document = template[..start].to_owned();
+            push_escaped_text(&mut document, title);
+            document.push_str(&template[end..]);
+            document
+        }
+        _ => template.to_owned(),
+    }
+}
+
+// ------ HTML ------
+
+/// Markup of `nodes`; their event handlers are attached once the app takes over.
+fn html(nodes: &[Node<Msg>]) -> String {
+    let mut html = String::new();
+    for node in nodes {
+        write_node(&mut html, node);
+    }
+    html
+}
+
+fn write_node(html: &mut String, node: &Node<Msg>) {
+    let element = match node {
+        Node::Element(element) => element,
+        Node::Text(text) => {
+            push_escaped_text(html, &text.text);
+            return;
+        }
+        Node::Empty | Node::NoChange => return,
+    };
+
+    let tag = element.tag.as_str();
+    write!(html, "<{}", tag).ok();
+    for (name, value) in &element.attrs.vals {
+        match value {
+            AtValue::Some(value) => {
+                write!(html, r#" {}=""#, name.as_str()).ok();
+                push_escaped_text(html, value);
+                html.push('"');
+            }
+            AtValue::None => {
+                write!(html, " {}", name.as_str()).ok();
+            }
+            AtValue::Ignored => (),
+        }
+    }
+    let style = element
+        .style
+        .vals
+        .iter()
+        .filter_map(|(name, value)| match value {
+            CSSValue::Some(value) => Some(format!("{}: {}", name.as_str(), value)),
+            CSSValue::Ignored => None,
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    if !style.is_empty() {
+        html.push_str(r#" style=""#);
+        push_escaped_text(html, &style);
+        html.push('"');
+    }
+    html.push('>');
+
+    if VOID_ELEMENTS.contains(&tag) {
+        return;
+    }
+    for child in &element.children {
+        write_node(html, child);
+    }
+    write!(html, "</{}>", tag).ok();
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{data, project, rust_tag};
+    use seed::*;
+    use serde_json::json;
+
+    /// Projects with names or tags that can't be path segments.
+    fn data_with_invalid_names() -> Data {
+        data(
+            rust_tag(),
+            json!([project("ash", &["rust", "../x"]), project("a/b", &["rust"])]),
+        )
+    }
+
+    #[test]
+    fn escapes_text_and_attributes() {
+        let node: Node<Msg> = a![
+            attrs! {At::Href => "/search?q=\"a\"&b", At::Title => "<b>"},
+            "<script> & \"quotes\""
+        ];
+        assert_eq!(
+            html(&[node]),
+            concat!(
+                r#"<a href="/search?q=&quot;a&quot;&amp;b" title="&lt;b&gt;">"#,
+                r#"&lt;script&gt; &amp; &quot;quotes&quot;</a>"#
+            )
+        );
+    }
+
+    #[test]
+    fn void_elements_and_style() {
+        let node: Node<Msg> = p![
+            "a",
+            br![],
+            img![
+                style! {St::Border => 0},
+                attrs! {At::Src => "/a.png", At::Alt => ""}
+            ],
+        ];
+        assert_eq!(
+            html(&[node]),
+            r#"<p>a<br><img src="/a.png" alt="" style="border: 0"></p>"#
+        );
+    }
+
+    #[test]
+    fn title() {
+        let template = "<head><title>Seed</title></head><body></body>";
+        assert_eq!(
+            with_title(template, "A & B"),
+            "<head><title>A &amp; B</title></head><body></body>"
+        );
+        assert_eq!(with_title("<body></body>", "A"), "<body></body>");
+    }
+
+    #[test]
+    fn routes_skip_invalid_path_segments() {
+        let files = routes(&data_with_invalid_names())
+            .into_iter()
+            .map(|route| route.file)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                "index.html",
+                "tags/index.html",
+                "tags/rust/index.html",
+                "projects/ash/index.html",
+                "newsletter/index.html",
+                "sponsorships/index.html",
+                "search/index.html",
+            ]
+        );
+    }
+
+    #[test]
+    fn page_data_cant_close_its_element() {
+        let mut data = data_with_invalid_names();
+        data.projects[0].description = "</script><script>alert(1)</script>".to_owned();
+        let element = page_data_element(&PageData {
+            data,
+            content: None,
+        })
+        .expect("serializable page data");
+        assert_eq!(element.matches("</script>").count(), 1);
+        assert!(element.ends_with("</script>"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Search query syntax.
+//!
+//! A query is a whitespace separated list of tokens:
+//! - `tag:rust` - projects tagged `rust` (by tag or its label),
+//! - `is:featured` - featured projects,
+//! - `name:cargo`, `description:vulkan` - projects whose field contains the value,
+//! - `-token` - negation of any of the above, `-word` excludes projects mentioning `word`,
+//! - `"two words"` - a quoted phrase; quoted tokens are never filters,
+//! - other words are free text terms ranked by `search`.
+
+use crate::{search::IndexedProject, Project};
+use std::fmt;
+
+const FILTER_KEYS: &str = "tag:, is:, name: or description:";
+
+#[derive(Default)]
+pub struct Query {
+    /// Lowercase free text terms.
+    pub terms: Vec<String>,
+    pub filters: Vec<Filter>,
+    pub errors: Vec<QueryError>,
+}
+
+pub enum Filter {
+    Tag(String),
+    Featured,
+    Name(String),
+    Description(String),
+    /// A negated free text term.
+    Text(String),
+    Not(Box<Self>),
+}
+
+pub struct QueryError {
+    pub token: String,
+    pub kind: QueryErrorKind,
+}
+
+pub enum QueryErrorKind {
+    MissingValue,
+    UnknownKey(String),
+    UnknownIsValue(String),
+    NothingToNegate,
+}
+
+pub fn parse(input: &str) -> Query {
+    let mut query = Query::default();
+    for token in tokenize(input) {
+        let (negated, body) = match token.strip_prefix('-') {
+            Some(body) => (true, body),
+            None => (false, token),
+        };
+        let quoted = body.starts_with('"');
+        let body = body.replace('"', "");
+        if body.is_empty() && !negated {
+            continue;
+        }
+        let filter = match parse_filter(&body, quoted) {
+            Ok(Some(filter)) => filter,
+            Ok(None) if negated => Filter::Text(body.to_lowercase()),
+            Ok(None) => {
+                query.terms.push(body.to_lowercase());
+                continue;
+            }
+            Err(kind) => {
+                query.errors.push(QueryError {
+                    token: token.to_owned(),
+                    kind,
+                });
+                continue;
+            }
+        };
+        query.filters.push(if negated {
+            Filter::Not(Box::new(filter))
+        } else {
+            filter
+        });
+    }
+    query
+}
+
+/// Splits `input` at whitespace outside of double quotes, so `"texture synthesis"` and
+/// `name:"cargo deny"` are single tokens. An unclosed quote extends to the end of the input.
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut token_start = None;
+    let mut in_quotes = false;
+    for (index, c) in input.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c.is_whitespace() && !in_quotes {
+            if let Some(start) = token_start.take() {
+                tokens.push(&input[start..index]);
+            }
+        } else if token_start.is_none() {
+            token_start = Some(index);
+        }
+    }
+    tokens.extend(token_start.map(|start| &input[start..]));
+    tokens
+}
+
+/// Returns `None` for free text terms; `token` is without quotes, `quoted` if it started
+/// with one.
+fn parse_filter(token: &str, quoted: bool) -> Result<Option<Filter>, QueryErrorKind> {
+    if token.is_empty() {
+        return Err(QueryErrorKind::NothingToNegate);
+    }
+    if quoted {
+        return Ok(None);
+    }
+    let (key, value) = match token.find(':') {
+        Some(colon) => (&token[..colon], &token[colon + 1..]),
+        None => return Ok(None),
+    };
+    // Words like `C++:` or `10:30` aren't meant as filters.
+    if key.is_empty() || !key.chars().all(char::is_alphabetic) {
+        return Ok(None);
+    }
+    let key = key.to_lowercase();
+    let value = value.to_lowercase();
+    if value.is_empty() {
+        return Err(QueryErrorKind::MissingValue);
+    }
+
+    match key.as_str() {
+        "tag" => Ok(Some(Filter::Tag(value))),
+        "is" if value == "featured" => Ok(Some(Filter::Featured)),
+        "is" => Err(QueryErrorKind::UnknownIsValue(value)),
+        "name" => Ok(Some(Filter::Name(value))),
+        "description" | "desc" => Ok(Some(Filter::Description(value))),
+        _ => Err(QueryErrorKind::UnknownKey(key)),
+    }
+}
+
+impl Filter {
+    /// `indexed_project` provides the lowercased fields of `project`.
+    pub fn matches(&self, project: &Project, indexed_project: &IndexedProject) -> bool {
+        match self {
+            Self::Tag(tag) => indexed_project.has_tag(tag),
+            Self::Featured => project.featured,
+            Self::Name(name) => indexed_project.name().contains(name.as_str()),
+            Self::Description(text) => indexed_project.description().contains(text.as_str()),
+            Self::Text(text) => {
+                indexed_project.name().contains(text.as_str())
+                    || indexed_project.description().contains(text.as_str())
+                    || indexed_project.has_tag(text)
+            }
+            Self::Not(filter) => !filter.matches(project, indexed_project),
+        }
+    }
+}
+
+impl fmt::Display for QueryErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingValue => write!(f, "the filter needs a value, e.g. tag:rust"),
+            Self::UnknownKey(key) => write!(f, "unknown filter \"{}:\", use {}", key, FILTER_KEYS),
+            Self::UnknownIsValue(value) => {
+                write!(
+                    f,
+                    "unknown value \"{}\", only is:featured is supported",
+                    value
+                )
+            }
+            Self::NothingToNegate => write!(f, "\"-\" has to be followed by a word or a filter"),
+        }
+    }
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_kinds(query: &Query) -> Vec<String> {
+        query
+            .errors
+            .iter()
+            .map(|error| format!("{}: {}", error.token, error.kind))
+            .collect()
+    }
+
+    #[test]
+    fn tag_filters() {
+        let query = parse("tag:Rust -tag:tools");
+        assert!(query.terms.is_empty());
+        assert!(query.errors.is_empty());
+        assert!(matches!(
+            query.filters.as_slice(),
+            [Filter::Tag(tag), Filter::Not(negated)]
+                if tag == "rust" && matches!(&**negated, Filter::Tag(tag) if tag == "tools")
+        ));
+    }
+
+    #[test]
+    fn featured_name_and_description_filters() {
+        let query = parse("is:featured name:Cargo desc:vulkan");
+        assert!(matches!(
+            query.filters.as_slice(),
+            [Filter::Featured, Filter::Name(name), Filter::Description(description)]
+                if name == "cargo" && description == "vulkan"
+        ));
+    }
+
+    #[test]
+    fn terms_and_negated_terms() {
+        let query = parse("  Texture   -Go ");
+        assert_eq!(query.terms, vec!["texture"]);
+        assert!(matches!(
+            query.filters.as_slice(),
+            [Filter::Not(negated)] if matches!(&**negated, Filter::Text(text) if text == "go")
+        ));
+    }
+
+    #[test]
+    fn quoted_terms() {
+        let query = parse(r#""Texture Synthesis" rust "tag:rust" name:"cargo deny""#);
+        assert_eq!(query.terms, vec!["texture synthesis", "rust", "tag:rust"]);
+        assert!(matches!(
+            query.filters.as_slice(),
+            [Filter::Name(name)] if name == "cargo deny"
+        ));
+        assert!(query.errors.is_empty());
+
+        let query = parse(r#"-"web ui" "" "physx rs"#);
+        assert_eq!(query.terms, vec!["physx rs"]);
+        assert!(matches!(
+            query.filters.as_slice(),
+            [Filter::Not(negated)] if matches!(&**negated, Filter::Text(text) if text == "web ui")
+        ));
+    }
+
+    #[test]
+    fn words_with_colons_are_terms() {
+        let query = parse("c++: 10:30");
+        assert_eq!(query.terms, vec!["c++:", "10:30"]);
+        assert!(query.filters.is_empty());
+        assert!(query.errors.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let query = parse("is:foo tag: Lang:rust - rust");
+        assert_eq!(query.terms, vec!["rust"]);
+        assert!(query.filters.is_empty());
+        assert_eq!(
+            error_kinds(&query),
+            vec![
+                "is:foo: unknown value \"foo\", only is:featured is supported",
+                "tag:: the filter needs a value, e.g. tag:rust",
+                "Lang:rust: unknown filter \"lang:\", use tag:, is:, name: or description:",
+                "-: \"-\" has to be followed by a word or a filter",
+            ]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use seed::{prelude::*, *};
+
+const STORAGE_KEY: &str = "opensource-website-recent-searches";
+const MAX_RECENT_SEARCHES: usize = 5;
+
+/// Recent search queries, the most recent first.
+pub fn load() -> Vec<String> {
+    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+}
+
+/// Moves `query` to the front of `recent_searches` and persists them.
+pub fn add(recent_searches: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+    recent_searches.retain(|recent_search| recent_search != query);
+    recent_searches.insert(0, query.to_owned());
+    recent_searches.truncate(MAX_RECENT_SEARCHES);
+    store(recent_searches);
+}
+
+pub fn clear(recent_searches: &mut Vec<String>) {
+    recent_searches.clear();
+    LocalStorage::remove(STORAGE_KEY).ok();
+}
+
+fn store(recent_searches: &[String]) {
+    if let Err(error) = LocalStorage::insert(STORAGE_KEY, &recent_searches) {
+        error!("cannot store recent searches:", error);
+    }
+}
@@ -0,0 +1,357 @@
+//! Allow-list for inline HTML in contributed project descriptions.
+//!
+//! Only links, emphasis and code are allowed; every other tag is dropped (keeping its text),
+//! except tags like `script` whose content is dropped as well. Anchors keep only a `href`
+//! with a safe scheme.
+
+const ALLOWED_TAGS: &[&str] = &["a", "b", "code", "em", "i", "strong"];
+/// Tags whose content must never be rendered, not even as text.
+const DROPPED_CONTENT_TAGS: &[&str] = &[
+    "iframe", "noscript", "object", "script", "style", "template", "textarea", "title",
+];
+const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+pub fn is_allowed_tag(name: &str) -> bool {
+    ALLOWED_TAGS.contains(&name)
+}
+
+pub fn is_dropped_content_tag(name: &str) -> bool {
+    DROPPED_CONTENT_TAGS.contains(&name)
+}
+
+// ------ Tags ------
+
+pub enum Tag {
+    Start { name: String, href: Option<String> },
+    End,
+    Other,
+}
+
+/// Parses the tag at the start of `input` and returns it with its length in bytes.
+/// `href` is kept only for anchors and only when it's safe.
+pub fn parse_tag(input: &str) -> Option<(Tag, usize)> {
+    if input.starts_with("<!--") {
+        return input.find("-->").map(|end| (Tag::Other, end + 3));
+    }
+    let mut chars = input.char_indices().skip(1).peekable();
+
+    match chars.peek() {
+        Some((_, c)) if *c == '!' || *c == '?' => {
+            return input.find('>').map(|end| (Tag::Other, end + 1));
+        }
+        Some((_, '/')) => {
+            chars.next();
+        }
+        Some((_, c)) if c.is_ascii_alphabetic() => (),
+        _ => return None,
+    }
+    let is_end_tag = input[1..].starts_with('/');
+
+    let name_start = chars.peek()?.0;
+    let mut name_end = name_start;
+    while let Some((index, c)) = chars.peek() {
+        if c.is_ascii_alphanumeric() {
+            chars.next();
+        } else {
+            name_end = *index;
+            break;
+        }
+    }
+    if name_end == name_start {
+        return None;
+    }
+    let name = input[name_start..name_end].to_ascii_lowercase();
+
+    let mut href = None;
+    let mut rest = &input[name_end..];
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+        if rest.starts_with('>') {
+            break;
+        }
+        let (attribute, value, attribute_len) = parse_attribute(rest)?;
+        if attribute == "href" && value.is_some() {
+            href = value.map(|value| decode_entities(&value));
+        }
+        rest = &rest[attribute_len..];
+    }
+    let tag_len = input.len() - rest.len() + 1;
+
+    let tag = if is_end_tag {
+        Tag::End
+    } else {
+        Tag::Start {
+            href: href.filter(|href| name == "a" && is_safe_url(href)),
+            name,
+        }
+    };
+    Some((tag, tag_len))
+}
+
+/// Returns the lowercased attribute name, its raw value and the attribute length in bytes.
+fn parse_attribute(input: &str) -> Option<(String, Option<String>, usize)> {
+    let name_len = input
+        .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+        .unwrap_or(input.len());
+    let name = input[..name_len].to_ascii_lowercase();
+
+    let after_name = input[name_len..].trim_start();
+    let after_equals = match after_name.strip_prefix('=') {
+        Some(after_equals) => after_equals.trim_start(),
+        None => return Some((name, None, name_len.max(1))),
+    };
+    let value_start = input.len() - after_equals.len();
+
+    let (value, value_len) = match after_equals.chars().next()? {
+        quote if quote == '"' || quote == '\'' => {
+            let end = after_equals[1..].find(quote)?;
+            (&after_equals[1..=end], end + 2)
+        }
+        _ => {
+            let end = after_equals
+                .find(|c: char| c.is_whitespace() || c == '>')
+                .unwrap_or(after_equals.len());
+            (&after_equals[..end], end)
+        }
+    };
+    Some((name, Some(value.to_owned()), value_start + value_len))
+}
+
+/// Returns the rest of `input` after the end tag `name`, or nothing if it isn't closed.
+pub fn skip_to_end_tag<'a>(input: &'a str, name: &str) -> &'a str {
+    let lowercase_input = input.to_ascii_lowercase();
+    let end_tag = format!("</{}", name);
+    lowercase_input
+        .find(&end_tag)
+        .and_then(|start| {
+            input[start..]
+                .find('>')
+                .map(|end| &input[start + end + 1..])
+        })
+        .unwrap_or("")
+}
+
+// ------ Urls ------
+
+/// Accepts relative URLs and absolute URLs with an allowed scheme.
+pub fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside schemes (`java\tscript:`).
+    let url = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>();
+
+    match url.find(&[':', '/', '?', '#'][..]) {
+        Some(index) if url[index..].starts_with(':') => {
+            let scheme = url[..index].to_ascii_lowercase();
+            SAFE_URL_SCHEMES.contains(&scheme.as_str())
+        }
+        _ => true,
+    }
+}
+
+// ------ Entities ------
+
+/// Escapes `text` for HTML text, HTML attribute values and XML.
+pub fn push_escaped_text(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Length of a well-formed character reference at the start of `input`, or 0.
+fn entity_len(input: &str) -> usize {
+    let body = &input[1..];
+    let body_len = if let Some(number) = body.strip_prefix('#') {
+        let digits = number.strip_prefix(&['x', 'X'][..]).map_or_else(
+            || number.chars().take_while(char::is_ascii_digit).count(),
+            |hex| hex.chars().take_while(char::is_ascii_hexdigit).count() + 1,
+        );
+        if digits == 0 {
+            return 0;
+        }
+        digits + 1
+    } else {
+        body.chars().take_while(char::is_ascii_alphanumeric).count()
+    };
+    if body_len > 0 && body[body_len..].starts_with(';') {
+        body_len + 2
+    } else {
+        0
+    }
+}
+
+pub fn decode_entities(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(position) = rest.find('&') {
+        output.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        let entity_len = entity_len(rest);
+        let decoded = if entity_len > 0 {
+            decode_entity(&rest[1..entity_len - 1])
+        } else {
+            None
+        };
+        if let Some(c) = decoded {
+            output.push(c);
+            rest = &rest[entity_len..];
+        } else {
+            output.push('&');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = number.strip_prefix(&['x', 'X'][..]).map_or_else(
+            || number.parse().ok(),
+            |hex| u32::from_str_radix(hex, 16).ok(),
+        )?;
+        return std::char::from_u32(code);
+    }
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "colon" => Some(':'),
+        "Tab" => Some('\t'),
+        "NewLine" => Some('\n'),
+        _ => None,
+    }
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Name and `href` of the start tag at the start of `input`.
+    fn start_tag(input: &str) -> Option<(String, Option<String>)> {
+        match parse_tag(input)? {
+            (Tag::Start { name, href }, len) if len == input.len() => Some((name, href)),
+            _ => None,
+        }
+    }
+
+    fn anchor(href: Option<&str>) -> Option<(String, Option<String>)> {
+        Some(("a".to_owned(), href.map(str::to_owned)))
+    }
+
+    #[test]
+    fn tag_names_are_lowercased() {
+        assert_eq!(start_tag("<SCRIPT>"), Some(("script".to_owned(), None)));
+        assert!(is_dropped_content_tag("script"));
+        assert!(!is_allowed_tag("img"));
+        assert!(is_allowed_tag("code"));
+    }
+
+    #[test]
+    fn only_safe_anchor_hrefs_are_kept() {
+        assert_eq!(
+            start_tag(r#"<a href="https://embark.dev">"#),
+            anchor(Some("https://embark.dev"))
+        );
+        assert_eq!(
+            start_tag(r#"<img href="/x.png">"#),
+            Some(("img".to_owned(), None))
+        );
+    }
+
+    #[test]
+    fn event_handlers_are_dropped() {
+        assert_eq!(
+            start_tag(r#"<a href="/" onmouseover='alert(1)'>"#),
+            anchor(Some("/"))
+        );
+        assert_eq!(
+            start_tag(r#"<b onclick="alert(1)">"#),
+            Some(("b".to_owned(), None))
+        );
+    }
+
+    #[test]
+    fn javascript_urls_are_dropped() {
+        let unsafe_anchors = [
+            r#"<a href="javascript:alert(1)">"#,
+            r#"<a href="JaVaScRiPt:alert(1)">"#,
+            r#"<a href=" javascript:alert(1)">"#,
+            r#"<a href="java&#x09;script:alert(1)">"#,
+            r#"<a href="&#106;avascript:alert(1)">"#,
+            r#"<a href="&#x6A;avascript&colon;alert(1)">"#,
+            r#"<a href="javascript&#58;alert(1)">"#,
+            r#"<a href="data:text/html;base64,PHNjcmlwdD4=">"#,
+        ];
+        for anchor_tag in &unsafe_anchors {
+            assert_eq!(start_tag(anchor_tag), anchor(None), "{}", anchor_tag);
+        }
+    }
+
+    #[test]
+    fn unquoted_attributes() {
+        assert_eq!(start_tag("<a href=javascript:alert(1)>"), anchor(None));
+        assert_eq!(
+            start_tag("<a href=https://embark.dev onclick=alert(1)>"),
+            anchor(Some("https://embark.dev"))
+        );
+        assert_eq!(
+            start_tag(r#"<a href=/x"onclick="alert(1)>"#),
+            anchor(Some(r#"/x"onclick="alert(1)"#))
+        );
+    }
+
+    #[test]
+    fn malformed_tags_are_text() {
+        assert!(parse_tag("< 2").is_none());
+        assert!(parse_tag("<a href=").is_none());
+        assert!(parse_tag(r#"<a href="/>"#).is_none());
+        assert!(matches!(
+            parse_tag("<!-- <script> -->b"),
+            Some((Tag::Other, 17))
+        ));
+    }
+
+    #[test]
+    fn dropped_content_ends_at_the_end_tag() {
+        assert_eq!(skip_to_end_tag("alert(1)</ScRiPt>b", "script"), "b");
+        assert_eq!(skip_to_end_tag("alert(1)", "script"), "");
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(decode_entities("&#x6A;avascript&colon;"), "javascript:");
+        assert_eq!(decode_entities("&amp; &#60; &bogus; &"), "& < &bogus; &");
+    }
+
+    #[test]
+    fn safe_urls() {
+        assert!(is_safe_url("https://embark.dev"));
+        assert!(is_safe_url("mailto:opensource@embark-studios.com"));
+        assert!(is_safe_url("/projects/kajiya"));
+        assert!(is_safe_url("#project-list"));
+        assert!(is_safe_url("page?next=javascript:alert(1)"));
+        assert!(!is_safe_url("vbscript:msgbox(1)"));
+        assert!(!is_safe_url("java\nscript:alert(1)"));
+        assert!(!is_safe_url("DATA:text/html,<script>alert(1)</script>"));
+    }
+}
@@ -0,0 +1,560 @@
+//! Ranked fuzzy search over projects.
+//!
+//! The query is parsed by `query::parse`; projects have to pass all its filters.
+//! Every free text term has to match at least one field. A term scores by the weight of the best
+//! field it matches (name > tags > description > extended description) and by the match
+//! quality (word prefix > substring > typo). Results are ordered by the sum of term scores.
+//!
+//! `SearchIndex` is built once per `Data`; it keeps the lowercased fields and inverted indices
+//! of trigrams and words, so only projects that can match a term are scored. Typos are looked
+//! for only in words sharing a trigram with the term.
+
+use crate::{markdown, query, suggest::edit_distance, Data, Project};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    iter,
+    ops::Range,
+};
+
+const NAME_WEIGHT: u32 = 8;
+const TAGS_WEIGHT: u32 = 4;
+const DESCRIPTION_WEIGHT: u32 = 2;
+const EXTENDED_DESCRIPTION_WEIGHT: u32 = 1;
+
+const WORD_PREFIX_QUALITY: u32 = 3;
+const SUBSTRING_QUALITY: u32 = 2;
+const TYPO_QUALITY: u32 = 1;
+
+pub struct SearchResult {
+    /// Index into `Data.projects`.
+    pub project: usize,
+    pub score: u32,
+    /// Byte ranges of the matched parts of the project name.
+    pub name_ranges: Vec<Range<usize>>,
+    /// Byte ranges of the matched parts of `SearchIndex::description`.
+    pub description_ranges: Vec<Range<usize>>,
+}
+
+type Trigram = [char; 3];
+
+#[derive(Default)]
+pub struct SearchIndex {
+    projects: Vec<IndexedProject>,
+    /// Projects containing the trigram in any of their fields.
+    trigrams: HashMap<Trigram, BTreeSet<usize>>,
+    /// Words of all fields with the projects containing them, for typo tolerant lookups.
+    words: Vec<(String, BTreeSet<usize>)>,
+    /// Indices into `words` of the words containing the trigram, see `word_trigrams`.
+    word_trigrams: HashMap<Trigram, BTreeSet<usize>>,
+}
+
+impl SearchIndex {
+    pub fn new(data: &Data) -> Self {
+        let mut index = Self::default();
+        let mut words = BTreeMap::<String, BTreeSet<usize>>::new();
+        for (project_index, project) in data.projects.iter().enumerate() {
+            let indexed_project = IndexedProject::new(project, data);
+            for field in indexed_project.fields() {
+                for trigram in trigrams(&field.text) {
+                    index
+                        .trigrams
+                        .entry(trigram)
+                        .or_default()
+                        .insert(project_index);
+                }
+                for (_, word) in field.words() {
+                    words
+                        .entry(word.to_owned())
+                        .or_default()
+                        .insert(project_index);
+                }
+            }
+            index.projects.push(indexed_project);
+        }
+        index.words = words.into_iter().collect();
+        for (word_index, (word, _)) in index.words.iter().enumerate() {
+            for trigram in word_trigrams(word) {
+                index
+                    .word_trigrams
+                    .entry(trigram)
+                    .or_default()
+                    .insert(word_index);
+            }
+        }
+        index
+    }
+
+    /// The description of the project at `project` without Markdown markup.
+    pub fn description(&self, project: usize) -> &str {
+        self.projects
+            .get(project)
+            .map_or("", |indexed_project| &indexed_project.description)
+    }
+
+    /// Projects matching `query`, the best matches first.
+    /// A query without free text terms keeps the projects in the order of the data file.
+    pub fn search(&self, query: &str, data: &Data) -> Vec<SearchResult> {
+        let query = query::parse(query);
+
+        let mut candidates: Option<BTreeSet<usize>> = None;
+        for term in &query.terms {
+            if let Some(term_candidates) = self.candidates(term) {
+                candidates = Some(match candidates {
+                    Some(candidates) => {
+                        candidates.intersection(&term_candidates).copied().collect()
+                    }
+                    None => term_candidates,
+                });
+            }
+        }
+        let candidates = candidates.map_or_else(
+            || (0..self.projects.len()).collect::<Vec<_>>(),
+            |candidates| candidates.into_iter().collect(),
+        );
+
+        let mut results = candidates
+            .into_iter()
+            .filter_map(|project_index| {
+                let project = data.projects.get(project_index)?;
+                let indexed_project = &self.projects[project_index];
+                if !query
+                    .filters
+                    .iter()
+                    .all(|filter| filter.matches(project, indexed_project))
+                {
+                    return None;
+                }
+                indexed_project.match_terms(project_index, &query.terms)
+            })
+            .collect::<Vec<_>>();
+        // Stable sort keeps equally scored projects in the order of the data file.
+        results.sort_by(|result_a, result_b| result_b.score.cmp(&result_a.score));
+        results
+    }
+
+    /// Projects that may match `term`; `None` when the term is too short to narrow them down.
+    fn candidates(&self, term: &str) -> Option<BTreeSet<usize>> {
+        let term_trigrams = trigrams(term);
+        if term_trigrams.is_empty() {
+            return None;
+        }
+        // Every trigram of a substring occurs in the text containing it.
+        let mut candidates = term_trigrams
+            .iter()
+            .map(|trigram| self.trigrams.get(trigram))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|mut trigram_projects| {
+                // Intersect the borrowed sets, starting with the smallest one.
+                trigram_projects.sort_by_key(|projects| projects.len());
+                let (smallest, others) = trigram_projects.split_first()?;
+                Some(
+                    smallest
+                        .iter()
+                        .filter(|project| others.iter().all(|other| other.contains(project)))
+                        .copied()
+                        .collect::<BTreeSet<_>>(),
+                )
+            })
+            .unwrap_or_default();
+
+        let max_typos = max_typos(term);
+        if max_typos > 0 {
+            let term_len = term.chars().count();
+            // Shorter words are too far even from the term's prefix.
+            let min_word_len = term_len.saturating_sub(max_typos);
+            let words = word_trigrams(term)
+                .iter()
+                .filter_map(|trigram| self.word_trigrams.get(trigram))
+                .flatten()
+                .collect::<BTreeSet<_>>();
+            for word_index in words {
+                let (word, projects) = &self.words[*word_index];
+                if word.chars().count() >= min_word_len
+                    && typo_distance(term, term_len, word).0 <= max_typos
+                {
+                    candidates.extend(projects);
+                }
+            }
+        }
+        Some(candidates)
+    }
+}
+
+fn trigrams(text: &str) -> Vec<Trigram> {
+    let chars = text.chars().collect::<Vec<_>>();
+    chars
+        .windows(3)
+        .map(|trigram| [trigram[0], trigram[1], trigram[2]])
+        .collect()
+}
+
+/// Trigrams of `word` padded by spaces, so words sharing their first or last two characters
+/// share a trigram even when a typo breaks all the trigrams inside them.
+fn word_trigrams(word: &str) -> Vec<Trigram> {
+    trigrams(&format!("  {}  ", word))
+}
+
+/// Typos allowed in a term; short terms have to match exactly.
+fn max_typos(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Edit distance between `term` and the whole `word` or its prefix of the same length,
+/// so a term still being typed matches too. Returns the distance and the matched length.
+fn typo_distance(term: &str, term_len: usize, word: &str) -> (usize, usize) {
+    let prefix_len = word
+        .char_indices()
+        .nth(term_len)
+        .map_or(word.len(), |(index, _)| index);
+    let word_distance = edit_distance(term, word);
+    let prefix_distance = edit_distance(term, &word[..prefix_len]);
+    if prefix_distance < word_distance {
+        (prefix_distance, prefix_len)
+    } else {
+        (word_distance, word.len())
+    }
+}
+
+// ------ IndexedProject ------
+
+pub struct IndexedProject {
+    name: Field,
+    /// The description without Markdown markup, displayed in search results.
+    description: String,
+    description_field: Field,
+    extended_description: Field,
+    /// Tags and their labels.
+    tags: Vec<Field>,
+}
+
+impl IndexedProject {
+    fn new(project: &Project, data: &Data) -> Self {
+        let description = markdown::plain_text(&project.description);
+        let extended_description = project
+            .extended_description
+            .as_deref()
+            .map(markdown::plain_text)
+            .unwrap_or_default();
+
+        Self {
+            name: Field::new(&project.name),
+            description_field: Field::new(&description),
+            description,
+            extended_description: Field::new(&extended_description),
+            tags: project
+                .tags
+                .iter()
+                .flat_map(|tag| iter::once(tag.as_str()).chain(iter::once(data.tag_label(tag))))
+                .map(Field::new)
+                .collect(),
+        }
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &Field> {
+        iter::once(&self.name)
+            .chain(iter::once(&self.description_field))
+            .chain(iter::once(&self.extended_description))
+            .chain(&self.tags)
+    }
+
+    /// Lowercased project name.
+    pub fn name(&self) -> &str {
+        &self.name.text
+    }
+
+    /// Lowercased description without Markdown markup.
+    pub fn description(&self) -> &str {
+        &self.description_field.text
+    }
+
+    /// Whether the lowercase `tag` is one of the project's tags or tag labels.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|tag_field| tag_field.text == tag)
+    }
+
+    fn match_terms(&self, project_index: usize, terms: &[String]) -> Option<SearchResult> {
+        let mut name_ranges = Vec::new();
+        let mut description_ranges = Vec::new();
+        let mut score = 0;
+        for term in terms {
+            let name_match = self.name.find(term);
+            let description_match = self.description_field.find(term);
+            let field_scores = [
+                name_match
+                    .as_ref()
+                    .map(|name_match| NAME_WEIGHT * name_match.quality),
+                self.tags
+                    .iter()
+                    .filter_map(|tag_field| tag_field.find(term))
+                    .map(|tag_match| TAGS_WEIGHT * tag_match.quality)
+                    .max(),
+                description_match
+                    .as_ref()
+                    .map(|description_match| DESCRIPTION_WEIGHT * description_match.quality),
+                self.extended_description
+                    .find(term)
+                    .map(|extended_match| EXTENDED_DESCRIPTION_WEIGHT * extended_match.quality),
+            ];
+            score += *field_scores.iter().flatten().max()?;
+            name_ranges.extend(name_match.map(|name_match| name_match.range));
+            description_ranges
+                .extend(description_match.map(|description_match| description_match.range));
+        }
+
+        Some(SearchResult {
+            project: project_index,
+            score,
+            name_ranges,
+            description_ranges,
+        })
+    }
+}
+
+// ------ Field ------
+
+struct TermMatch {
+    quality: u32,
+    /// Byte range in the original text.
+    range: Range<usize>,
+}
+
+/// Lowercased text of a searched field.
+struct Field {
+    text: String,
+    /// Byte offset in the original text for every byte of `text`, plus the original length.
+    offsets: Vec<usize>,
+}
+
+impl Field {
+    fn new(original: &str) -> Self {
+        let mut text = String::with_capacity(original.len());
+        let mut offsets = Vec::with_capacity(original.len() + 1);
+        for (offset, c) in original.char_indices() {
+            for lowercase_char in c.to_lowercase() {
+                text.push(lowercase_char);
+                offsets.resize(text.len(), offset);
+            }
+        }
+        offsets.push(original.len());
+        Self { text, offsets }
+    }
+
+    fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        self.offsets[range.start]..self.offsets[range.end]
+    }
+
+    /// Finds the best match of the lowercase `term`.
+    fn find(&self, term: &str) -> Option<TermMatch> {
+        let mut best_quality = None;
+        for (start, _) in self.text.match_indices(term) {
+            let quality = if self.is_word_start(start) {
+                WORD_PREFIX_QUALITY
+            } else {
+                SUBSTRING_QUALITY
+            };
+            if best_quality.map_or(true, |(best_quality, _)| quality > best_quality) {
+                best_quality = Some((quality, start));
+            }
+            if quality == WORD_PREFIX_QUALITY {
+                break;
+            }
+        }
+        if let Some((quality, start)) = best_quality {
+            return Some(TermMatch {
+                quality,
+                range: self.original_range(start..start + term.len()),
+            });
+        }
+        self.find_with_typos(term)
+    }
+
+    fn find_with_typos(&self, term: &str) -> Option<TermMatch> {
+        let max_typos = max_typos(term);
+        if max_typos == 0 {
+            return None;
+        }
+        let term_len = term.chars().count();
+
+        self.words()
+            .into_iter()
+            .map(|(start, word)| {
+                let (typos, len) = typo_distance(term, term_len, word);
+                (typos, start..start + len)
+            })
+            .filter(|(typos, _)| *typos <= max_typos)
+            .min_by_key(|(typos, _)| *typos)
+            .map(|(_, range)| TermMatch {
+                quality: TYPO_QUALITY,
+                range: self.original_range(range),
+            })
+    }
+
+    fn is_word_start(&self, index: usize) -> bool {
+        self.text[..index]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric())
+    }
+
+    /// Alphanumeric words with their byte offsets.
+    fn words(&self) -> Vec<(usize, &str)> {
+        let mut words = Vec::new();
+        let mut word_start = None;
+        for (index, c) in self
+            .text
+            .char_indices()
+            .chain(iter::once((self.text.len(), ' ')))
+        {
+            match (word_start, c.is_alphanumeric()) {
+                (None, true) => word_start = Some(index),
+                (Some(start), false) => {
+                    words.push((start, &self.text[start..index]));
+                    word_start = None;
+                }
+                _ => (),
+            }
+        }
+        words
+    }
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{self, project};
+    use serde_json::json;
+
+    fn data(projects: &[(&str, &[&str], &str)]) -> Data {
+        let projects = projects
+            .iter()
+            .map(|(name, tags, description)| {
+                let mut project = project(name, tags);
+                project["description"] = json!(description);
+                project
+            })
+            .collect::<Vec<_>>();
+        test_data::data(json!({}), json!(projects))
+    }
+
+    fn search(query: &str, data: &Data) -> Vec<SearchResult> {
+        SearchIndex::new(data).search(query, data)
+    }
+
+    #[test]
+    fn lowercasing_that_changes_lengths() {
+        // 'İ' (2 bytes) is lowercased to 'i' and a combining dot (3 bytes).
+        let field = Field::new("İstanbul");
+        assert_eq!(field.text, "i\u{307}stanbul");
+        let start = field.text.find("stan").unwrap();
+        assert_eq!(field.original_range(start..start + 4), 2..6);
+        assert_eq!(field.original_range(0..3), 0..2);
+        assert_eq!(
+            field.original_range(0..field.text.len()),
+            0.."İstanbul".len()
+        );
+
+        let term_match = field.find("stan").unwrap();
+        assert_eq!(&"İstanbul"[term_match.range], "stan");
+    }
+
+    #[test]
+    fn name_matches_rank_above_tags_and_tags_above_descriptions() {
+        let data = data(&[
+            ("ash", &["graphics"], "Vulkan bindings for Rust"),
+            ("physx-rs", &["rust"], "Bindings for PhysX"),
+            ("rust-gpu", &["graphics"], "Shaders"),
+        ]);
+        let results = search("rust", &data);
+        let projects = results
+            .iter()
+            .map(|result| result.project)
+            .collect::<Vec<_>>();
+        assert_eq!(projects, vec![2, 1, 0]);
+        assert_eq!(results[0].name_ranges, vec![0..4]);
+        assert_eq!(results[2].description_ranges, vec![20..24]);
+    }
+
+    #[test]
+    fn word_prefixes_rank_above_substrings() {
+        let data = data(&[("cargo-fetcher", &["tools"], ""), ("fetch", &["tools"], "")]);
+        let projects = search("fetch", &data)
+            .iter()
+            .map(|result| result.project)
+            .collect::<Vec<_>>();
+        // Both names start with the term, the data file order is kept.
+        assert_eq!(projects, vec![0, 1]);
+
+        let projects = search("arg", &data)
+            .iter()
+            .map(|result| result.project)
+            .collect::<Vec<_>>();
+        assert_eq!(projects, vec![0]);
+    }
+
+    #[test]
+    fn all_terms_have_to_match() {
+        let data = data(&[
+            ("tame-oauth", &["rust"], "Small OAuth crate"),
+            (
+                "tame-gcs",
+                &["rust", "gcp"],
+                "Google Cloud Storage operations",
+            ),
+        ]);
+        let results = search("tame storage", &data);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].project, 1);
+    }
+
+    #[test]
+    fn quoted_phrases_match_as_a_whole() {
+        let data = data(&[
+            ("ash", &["graphics"], "Vulkan bindings for Rust"),
+            ("physx-rs", &["rust"], "Rust bindings for PhysX"),
+        ]);
+        let results = search(r#""rust bindings""#, &data);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].project, 1);
+        assert_eq!(results[0].description_ranges, vec![0..13]);
+    }
+
+    #[test]
+    fn typos() {
+        let data = data(&[
+            (
+                "texture-synthesis",
+                &["rust"],
+                "Example-based texture synthesis",
+            ),
+            (
+                "cargo-deny",
+                &["rust", "tools"],
+                "Cargo plugin for dependency graphs",
+            ),
+        ]);
+        let results = search("texure", &data);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].project, 0);
+        assert_eq!(results[0].name_ranges, vec![0..7]);
+        assert_eq!(results[0].score, NAME_WEIGHT * TYPO_QUALITY);
+
+        // A term being typed matches the prefix of a word.
+        let results = search("dependn", &data);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].project, 1);
+
+        // Short terms have to match exactly.
+        assert!(search("crg", &data).is_empty());
+        // Swapped characters are a single typo, but a seven character term allows only one.
+        assert_eq!(search("txeture", &data).len(), 1);
+        assert!(search("tixtora", &data).is_empty());
+    }
+}
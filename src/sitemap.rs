@@ -0,0 +1,109 @@
+//! `sitemap.xml` and `robots.txt`, written to the dist folder by `src/bin/sitemap.rs`.
+//!
+//! Both list the routes of `prerender::routes`, so crawlers find the pages selected by search
+//! parameters (e.g. `/tags?tag=rust`) that no link without JavaScript leads to.
+
+use crate::{
+    href::Href, prerender, sanitize::push_escaped_text, validation::is_valid_site_url, Data,
+};
+use std::fmt::Write;
+
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+pub const ROBOTS_FILE: &str = "robots.txt";
+
+/// The sitemap of indexed routes at `site_url`, usually `site.url`;
+/// `None` when `site_url` isn't an absolute URL.
+pub fn sitemap(data: &Data, site_url: &str) -> Option<String> {
+    let site_url = trim_site_url(site_url)?;
+    let mut sitemap = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+        "\n",
+    ));
+    for route in prerender::routes(data) {
+        if route.indexed {
+            sitemap.push_str("  <url><loc>");
+            push_escaped_text(&mut sitemap, site_url);
+            push_escaped_text(&mut sitemap, &route.url.href());
+            sitemap.push_str("</loc></url>\n");
+        }
+    }
+    sitemap.push_str("</urlset>\n");
+    Some(sitemap)
+}
+
+/// Disallows routes that shouldn't be indexed and points to the sitemap at `site_url`;
+/// `None` when `site_url` isn't an absolute URL.
+pub fn robots(data: &Data, site_url: &str) -> Option<String> {
+    let site_url = trim_site_url(site_url)?;
+    let mut robots = String::from("User-agent: *\n");
+    for route in prerender::routes(data) {
+        if !route.indexed {
+            writeln!(robots, "Disallow: {}", route.url.href()).ok();
+        }
+    }
+    writeln!(robots, "\nSitemap: {}/{}", site_url, SITEMAP_FILE).ok();
+    Some(robots)
+}
+
+/// `site_url` without the trailing slash.
+fn trim_site_url(site_url: &str) -> Option<&str> {
+    if is_valid_site_url(site_url) {
+        Some(site_url.trim_end_matches('/'))
+    } else {
+        None
+    }
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{data, project};
+    use serde_json::json;
+
+    fn cpp_data() -> Data {
+        data(
+            json!({"c++": {"label": "C++"}}),
+            json!([project("ash", &["c++"])]),
+        )
+    }
+
+    #[test]
+    fn sitemap_lists_indexed_routes() {
+        assert_eq!(
+            sitemap(&cpp_data(), "https://embark.dev/").as_deref(),
+            Some(concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "\n",
+                r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+                "\n",
+                "  <url><loc>https://embark.dev/</loc></url>\n",
+                "  <url><loc>https://embark.dev/tags</loc></url>\n",
+                "  <url><loc>https://embark.dev/tags?tag=c%2B%2B</loc></url>\n",
+                "  <url><loc>https://embark.dev/projects/ash</loc></url>\n",
+                "  <url><loc>https://embark.dev/newsletter</loc></url>\n",
+                "  <url><loc>https://embark.dev/sponsorships</loc></url>\n",
+                "</urlset>\n",
+            ))
+        );
+    }
+
+    #[test]
+    fn robots_disallows_search() {
+        assert_eq!(
+            robots(&cpp_data(), "https://embark.dev").as_deref(),
+            Some("User-agent: *\nDisallow: /search\n\nSitemap: https://embark.dev/sitemap.xml\n")
+        );
+    }
+
+    #[test]
+    fn relative_site_url() {
+        assert_eq!(sitemap(&cpp_data(), "embark.dev"), None);
+        assert_eq!(robots(&cpp_data(), ""), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! "Did you mean ...?" suggestions for mistyped tags and project names.
+
+/// Returns the candidate closest to `query`, ignoring case, if it's close enough to be a typo.
+pub fn closest<'a>(query: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let query = query.to_lowercase();
+    let query_len = query.chars().count();
+    if query_len == 0 {
+        return None;
+    }
+    // Roughly one typo per three characters.
+    let max_distance = ((query_len + 2) / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&query, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance where swapping two adjacent characters counts as one edit.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // `rows[i][j]` is the distance between the first `i` chars of `a` and `j` chars of `b`.
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution_cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + substitution_cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("", "rust"), 4);
+        assert_eq!(edit_distance("rust", "rust"), 0);
+        assert_eq!(edit_distance("rust", "rast"), 1);
+        assert_eq!(edit_distance("rust", "rsut"), 1);
+        assert_eq!(edit_distance("gcp", "gke"), 2);
+        assert_eq!(edit_distance("tools", "tool"), 1);
+    }
+
+    #[test]
+    fn closest_ignores_case() {
+        assert_eq!(closest("Rust", vec!["go", "rust"]), Some("rust"));
+        assert_eq!(
+            closest("cargo-dney", vec!["cargo-deny"]),
+            Some("cargo-deny")
+        );
+    }
+
+    #[test]
+    fn no_suggestion_for_empty_input() {
+        assert_eq!(closest("", vec!["", "go"]), None);
+        assert_eq!(closest("rust", Vec::new()), None);
+    }
+
+    #[test]
+    fn first_of_equally_close_candidates() {
+        assert_eq!(closest("gp", vec!["go", "gcp"]), Some("go"));
+        assert_eq!(closest("gp", vec!["gcp", "go"]), Some("gcp"));
+    }
+
+    #[test]
+    fn distance_threshold() {
+        // One edit per three characters, at least one.
+        assert_eq!(closest("ry", vec!["rust"]), None);
+        assert_eq!(closest("rus", vec!["rust"]), Some("rust"));
+        assert_eq!(closest("web", vec!["wg-ui"]), None);
+        assert_eq!(closest("dokcer", vec!["docker"]), Some("docker"));
+        assert_eq!(
+            closest("dockre-windows", vec!["docker-windows"]),
+            Some("docker-windows")
+        );
+        assert_eq!(closest("kubernetes", vec!["go"]), None);
+    }
+}
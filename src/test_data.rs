@@ -0,0 +1,37 @@
+//! `Data` fixtures shared by unit tests.
+
+use crate::Data;
+use serde_json::{json, Value};
+
+/// Valid site config with the given `tags` and `projects`.
+pub fn data(tags: Value, projects: Value) -> Data {
+    serde_json::from_value(json!({
+        "site": {
+            "title": "Open Source",
+            "orgName": "EmbarkStudios",
+            "forgeUrl": "https://github.com",
+            "homepage": "https://embark-studios.com",
+            "url": "https://embark.dev",
+            "logo": "/public/img/logo.png",
+            "socialLinks": [],
+        },
+        "tags": tags,
+        "projects": projects,
+    }))
+    .expect("valid data")
+}
+
+/// A valid project; tests set the fields they're about on the returned value.
+pub fn project(name: &str, tags: &[&str]) -> Value {
+    json!({
+        "name": name,
+        "emoji": "🦀",
+        "tags": tags,
+        "description": "A crate.",
+        "added": "2020-01-01",
+    })
+}
+
+pub fn rust_tag() -> Value {
+    json!({"rust": {"label": "Rust"}})
+}
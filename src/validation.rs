@@ -0,0 +1,667 @@
+use crate::{sanitize, Data, HomeSection, NewsletterEdition, Project, Sponsorship, TagInfo};
+use std::collections::HashSet;
+use std::fmt;
+
+// ------ ------
+//     Issue
+// ------ ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub subject: Subject,
+    pub field: Field,
+    pub kind: IssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    Site,
+    Project { index: usize, name: String },
+    Tag(String),
+    HomeSection(usize),
+    NewsletterEdition(usize),
+    Sponsorship { index: usize, recipient: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Tags,
+    FeatureImage,
+    ExtendedDescription,
+    Links,
+    Label,
+    Color,
+    Logo,
+    Cta,
+    Title,
+    Url,
+    Recipient,
+    Avatar,
+    Added,
+    Date,
+    StartDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    Missing,
+    /// Missing or malformed; listings put items without a date last.
+    MissingDate,
+    Empty,
+    Duplicate,
+    UnknownTag(String),
+    MalformedUrl(String),
+    MalformedColor(String),
+    /// The name can't be a part of URL paths and of the paths of prerendered pages.
+    InvalidPathSegment,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}: {}", self.subject, self.field, self.kind)
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Site => f.write_str("site"),
+            Self::Project { index, name } if name.is_empty() => write!(f, "project #{}", index),
+            Self::Project { name, .. } => write!(f, "project '{}'", name),
+            Self::Tag(tag) => write!(f, "tag '{}'", tag),
+            Self::HomeSection(index) => write!(f, "home section #{}", index),
+            Self::NewsletterEdition(index) => write!(f, "newsletter edition #{}", index),
+            Self::Sponsorship { index, recipient } if recipient.is_empty() => {
+                write!(f, "sponsorship #{}", index)
+            }
+            Self::Sponsorship { recipient, .. } => write!(f, "sponsorship '{}'", recipient),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::Tags => "tags",
+            Self::FeatureImage => "featureImage",
+            Self::ExtendedDescription => "extendedDescription",
+            Self::Links => "links",
+            Self::Label => "label",
+            Self::Color => "color",
+            Self::Logo => "logo",
+            Self::Cta => "cta",
+            Self::Title => "title",
+            Self::Url => "url",
+            Self::Recipient => "recipient",
+            Self::Avatar => "avatar",
+            Self::Added => "added",
+            Self::Date => "date",
+            Self::StartDate => "startDate",
+        })
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("missing (required for featured projects)"),
+            Self::MissingDate => f.write_str("missing or not a YYYY-MM-DD date"),
+            Self::Empty => f.write_str("empty"),
+            Self::Duplicate => f.write_str("duplicate"),
+            Self::UnknownTag(tag) => write!(f, "tag '{}' isn't declared in `tags`", tag),
+            Self::MalformedUrl(url) => write!(f, "malformed URL '{}'", url),
+            Self::MalformedColor(color) => write!(f, "malformed color '{}'", color),
+            Self::InvalidPathSegment => {
+                f.write_str("mustn't contain '/', '\\', '?' or '#' nor be empty, '.' or '..'")
+            }
+        }
+    }
+}
+
+// ------ ------
+//   Validate
+// ------ ------
+
+/// Checks the site config, all tags, home sections, newsletter editions, sponsorships and
+/// projects in `data` and returns every problem found.
+pub fn validate(data: &Data) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    validate_site_url(data.site_url(), |field, kind| {
+        issues.push(Issue {
+            subject: Subject::Site,
+            field,
+            kind,
+        });
+    });
+
+    for (tag, tag_info) in &data.tags {
+        validate_tag(tag, tag_info, |field, kind| {
+            issues.push(Issue {
+                subject: Subject::Tag(tag.clone()),
+                field,
+                kind,
+            });
+        });
+    }
+
+    for (index, home_section) in data.home_sections.iter().enumerate() {
+        validate_home_section(home_section, data, |field, kind| {
+            issues.push(Issue {
+                subject: Subject::HomeSection(index),
+                field,
+                kind,
+            });
+        });
+    }
+
+    for (index, edition) in data.newsletter_editions.iter().enumerate() {
+        validate_newsletter_edition(edition, |field, kind| {
+            issues.push(Issue {
+                subject: Subject::NewsletterEdition(index),
+                field,
+                kind,
+            });
+        });
+    }
+
+    for (index, sponsorship) in data.sponsorships.iter().enumerate() {
+        validate_sponsorship(sponsorship, |field, kind| {
+            issues.push(Issue {
+                subject: Subject::Sponsorship {
+                    index,
+                    recipient: sponsorship.recipient.clone(),
+                },
+                field,
+                kind,
+            });
+        });
+    }
+
+    let mut seen_names = HashSet::new();
+    for (index, project) in data.projects.iter().enumerate() {
+        validate_project(project, data, &mut seen_names, |field, kind| {
+            issues.push(Issue {
+                subject: Subject::Project {
+                    index,
+                    name: project.name.clone(),
+                },
+                field,
+                kind,
+            });
+        });
+    }
+    issues
+}
+
+fn validate_site_url(site_url: &str, mut report: impl FnMut(Field, IssueKind)) {
+    if site_url.trim().is_empty() {
+        report(Field::Url, IssueKind::Empty);
+    } else if !is_valid_site_url(site_url) {
+        report(Field::Url, IssueKind::MalformedUrl(site_url.to_owned()));
+    }
+}
+
+fn validate_tag(tag: &str, tag_info: &TagInfo, mut report: impl FnMut(Field, IssueKind)) {
+    if !is_valid_path_segment(tag) {
+        report(Field::Name, IssueKind::InvalidPathSegment);
+    }
+    if tag_info.label.trim().is_empty() {
+        report(Field::Label, IssueKind::Empty);
+    }
+    if let Some(color) = &tag_info.color {
+        if !is_valid_color(color) {
+            report(Field::Color, IssueKind::MalformedColor(color.clone()));
+        }
+    }
+}
+
+/// `seen_names` are the names of the preceding projects.
+fn validate_project<'a>(
+    project: &'a Project,
+    data: &Data,
+    seen_names: &mut HashSet<&'a str>,
+    mut report: impl FnMut(Field, IssueKind),
+) {
+    if project.name.trim().is_empty() {
+        report(Field::Name, IssueKind::Empty);
+    } else if !is_valid_path_segment(&project.name) {
+        report(Field::Name, IssueKind::InvalidPathSegment);
+    } else if !seen_names.insert(project.name.as_str()) {
+        report(Field::Name, IssueKind::Duplicate);
+    }
+
+    if project.tags.is_empty() {
+        report(Field::Tags, IssueKind::Empty);
+    }
+    for tag in &project.tags {
+        if !data.tags.contains_key(tag) {
+            report(Field::Tags, IssueKind::UnknownTag(tag.clone()));
+        }
+    }
+
+    if let Some(feature_image) = &project.feature_image {
+        if !is_valid_image_url(feature_image) {
+            report(
+                Field::FeatureImage,
+                IssueKind::MalformedUrl(feature_image.clone()),
+            );
+        }
+    }
+    if project.added.is_none() {
+        report(Field::Added, IssueKind::MissingDate);
+    }
+    for link in &project.links {
+        if link.url.trim().is_empty() {
+            report(Field::Links, IssueKind::Empty);
+        } else if !sanitize::is_safe_url(&link.url) {
+            report(Field::Links, IssueKind::MalformedUrl(link.url.clone()));
+        }
+    }
+
+    validate_featured(project, report);
+}
+
+fn validate_home_section(
+    home_section: &HomeSection,
+    data: &Data,
+    mut report: impl FnMut(Field, IssueKind),
+) {
+    if home_section.tags.is_empty() {
+        report(Field::Tags, IssueKind::Empty);
+    }
+    for tag in &home_section.tags {
+        if !data.tags.contains_key(tag) {
+            report(Field::Tags, IssueKind::UnknownTag(tag.clone()));
+        }
+    }
+    if let Some(logo) = &home_section.logo {
+        if !is_valid_image_url(logo) {
+            report(Field::Logo, IssueKind::MalformedUrl(logo.clone()));
+        }
+    }
+    if let Some(cta) = &home_section.cta {
+        if !sanitize::is_safe_url(&cta.url) {
+            report(Field::Cta, IssueKind::MalformedUrl(cta.url.clone()));
+        }
+    }
+}
+
+fn validate_newsletter_edition(
+    edition: &NewsletterEdition,
+    mut report: impl FnMut(Field, IssueKind),
+) {
+    if edition.title.trim().is_empty() {
+        report(Field::Title, IssueKind::Empty);
+    }
+    if edition.date.is_none() {
+        report(Field::Date, IssueKind::MissingDate);
+    }
+    if !sanitize::is_safe_url(&edition.url) {
+        report(Field::Url, IssueKind::MalformedUrl(edition.url.clone()));
+    }
+}
+
+fn validate_sponsorship(sponsorship: &Sponsorship, mut report: impl FnMut(Field, IssueKind)) {
+    if sponsorship.recipient.trim().is_empty() {
+        report(Field::Recipient, IssueKind::Empty);
+    }
+    if !sanitize::is_safe_url(&sponsorship.url) {
+        report(Field::Url, IssueKind::MalformedUrl(sponsorship.url.clone()));
+    }
+    if let Some(avatar) = &sponsorship.avatar {
+        if !is_valid_image_url(avatar) {
+            report(Field::Avatar, IssueKind::MalformedUrl(avatar.clone()));
+        }
+    }
+    if sponsorship.start_date.is_none() {
+        report(Field::StartDate, IssueKind::MissingDate);
+    }
+}
+
+fn validate_featured(project: &Project, mut report: impl FnMut(Field, IssueKind)) {
+    if !project.featured {
+        return;
+    }
+    if project.feature_image.is_none() {
+        report(Field::FeatureImage, IssueKind::Missing);
+    }
+    match &project.extended_description {
+        None => report(Field::ExtendedDescription, IssueKind::Missing),
+        Some(description) if description.trim().is_empty() => {
+            report(Field::ExtendedDescription, IssueKind::Empty);
+        }
+        Some(_) => (),
+    }
+}
+
+/// The site URL prefixes the sitemap's URLs, so it has to be an absolute http(s) URL without
+/// a path, search or hash.
+pub fn is_valid_site_url(url: &str) -> bool {
+    let host = match url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    {
+        Some(rest) => rest.strip_suffix('/').unwrap_or(rest),
+        None => return false,
+    };
+    !host.is_empty()
+        && !host
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '/' | '?' | '#' | '\\'))
+}
+
+/// Image URLs end up in CSS `url(...)`, so they have to be absolute http(s) URLs or root-relative paths
+/// without whitespace, quotes or parentheses.
+pub fn is_valid_image_url(url: &str) -> bool {
+    let rest = if let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    {
+        rest
+    } else if let Some(rest) = url.strip_prefix('/') {
+        rest
+    } else {
+        return false;
+    };
+    !rest.is_empty()
+        && !rest.starts_with('/')
+        && !rest
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | '\\'))
+}
+
+/// Tag and project names are path segments of their pages, so they can't contain separators,
+/// the search or hash delimiters or control characters, nor be empty, `.` or `..`.
+pub fn is_valid_path_segment(segment: &str) -> bool {
+    !matches!(segment, "" | "." | "..")
+        && !segment
+            .chars()
+            .any(|c| c.is_control() || matches!(c, '/' | '\\' | '?' | '#'))
+}
+
+/// Only hex colors (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`) are accepted.
+pub fn is_valid_color(color: &str) -> bool {
+    color.strip_prefix('#').map_or(false, |hex| {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+// ------ ------
+//     Tests
+// ------ ------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{data, project, rust_tag};
+    use serde_json::json;
+
+    fn project_issue(index: usize, name: &str, field: Field, kind: IssueKind) -> Issue {
+        Issue {
+            subject: Subject::Project {
+                index,
+                name: name.to_owned(),
+            },
+            field,
+            kind,
+        }
+    }
+
+    #[test]
+    fn valid_data_has_no_issues() {
+        let data = data(
+            rust_tag(),
+            json!([project("cargo-deny", &["rust"]), project("ash", &["rust"])]),
+        );
+        assert_eq!(validate(&data), Vec::new());
+    }
+
+    #[test]
+    fn site_url() {
+        let mut data = data(rust_tag(), json!([project("ash", &["rust"])]));
+        data.site.url = "embark.dev".to_owned();
+        let site_issue = |kind| Issue {
+            subject: Subject::Site,
+            field: Field::Url,
+            kind,
+        };
+        assert_eq!(
+            validate(&data),
+            vec![site_issue(IssueKind::MalformedUrl("embark.dev".to_owned()))]
+        );
+        data.site.url = String::new();
+        assert_eq!(validate(&data), vec![site_issue(IssueKind::Empty)]);
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let data = data(
+            rust_tag(),
+            json!([
+                project("ash", &["rust"]),
+                project("cargo-deny", &["rust"]),
+                project("ash", &["rust"]),
+            ]),
+        );
+        assert_eq!(
+            validate(&data),
+            vec![project_issue(2, "ash", Field::Name, IssueKind::Duplicate)]
+        );
+    }
+
+    #[test]
+    fn empty_name_and_tags() {
+        let data = data(rust_tag(), json!([project(" ", &[])]));
+        assert_eq!(
+            validate(&data),
+            vec![
+                project_issue(0, " ", Field::Name, IssueKind::Empty),
+                project_issue(0, " ", Field::Tags, IssueKind::Empty),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_added_date() {
+        let mut project = project("ash", &["rust"]);
+        project.as_object_mut().unwrap().remove("added");
+        let data = data(rust_tag(), json!([project]));
+        assert_eq!(
+            validate(&data),
+            vec![project_issue(
+                0,
+                "ash",
+                Field::Added,
+                IssueKind::MissingDate
+            )]
+        );
+    }
+
+    #[test]
+    fn unknown_tags() {
+        let data = data(rust_tag(), json!([project("ash", &["rust", "vulkan"])]));
+        assert_eq!(
+            validate(&data),
+            vec![project_issue(
+                0,
+                "ash",
+                Field::Tags,
+                IssueKind::UnknownTag("vulkan".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn names_that_cant_be_paths() {
+        let data = data(
+            json!({"a/b": {"label": "A/B"}}),
+            json!([project("..", &["a/b"]), project("ash?", &["a/b"])]),
+        );
+        assert_eq!(
+            validate(&data),
+            vec![
+                Issue {
+                    subject: Subject::Tag("a/b".to_owned()),
+                    field: Field::Name,
+                    kind: IssueKind::InvalidPathSegment,
+                },
+                project_issue(0, "..", Field::Name, IssueKind::InvalidPathSegment),
+                project_issue(1, "ash?", Field::Name, IssueKind::InvalidPathSegment),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_feature_image_urls() {
+        let mut project = project("ash", &["rust"]);
+        project["featureImage"] = json!("javascript:alert(1)");
+        let data = data(rust_tag(), json!([project]));
+        assert_eq!(
+            validate(&data),
+            vec![project_issue(
+                0,
+                "ash",
+                Field::FeatureImage,
+                IssueKind::MalformedUrl("javascript:alert(1)".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn empty_and_unsafe_link_urls() {
+        let mut project = project("ash", &["rust"]);
+        project["links"] = json!([
+            {"title": "Docs", "url": "https://docs.rs/ash"},
+            {"title": "Blog", "url": " "},
+            {"title": "Demo", "url": "javascript:alert(1)"},
+        ]);
+        let data = data(rust_tag(), json!([project]));
+        assert_eq!(
+            validate(&data),
+            vec![
+                project_issue(0, "ash", Field::Links, IssueKind::Empty),
+                project_issue(
+                    0,
+                    "ash",
+                    Field::Links,
+                    IssueKind::MalformedUrl("javascript:alert(1)".to_owned())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn featured_projects_need_image_and_extended_description() {
+        let mut incomplete = project("ash", &["rust"]);
+        incomplete["featured"] = json!(true);
+        let mut blank = project("rpmalloc-rs", &["rust"]);
+        blank["featured"] = json!(true);
+        blank["featureImage"] = json!("/public/img/rpmalloc.png");
+        blank["extendedDescription"] = json!("  ");
+        let data = data(rust_tag(), json!([incomplete, blank]));
+        assert_eq!(
+            validate(&data),
+            vec![
+                project_issue(0, "ash", Field::FeatureImage, IssueKind::Missing),
+                project_issue(0, "ash", Field::ExtendedDescription, IssueKind::Missing),
+                project_issue(
+                    1,
+                    "rpmalloc-rs",
+                    Field::ExtendedDescription,
+                    IssueKind::Empty
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tag_labels_and_colors() {
+        let data = data(
+            json!({"rust": {"label": "", "color": "red"}}),
+            json!([project("ash", &["rust"])]),
+        );
+        let tag_issue = |field, kind| Issue {
+            subject: Subject::Tag("rust".to_owned()),
+            field,
+            kind,
+        };
+        assert_eq!(
+            validate(&data),
+            vec![
+                tag_issue(Field::Label, IssueKind::Empty),
+                tag_issue(Field::Color, IssueKind::MalformedColor("red".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_newsletter_and_sponsorship_dates() {
+        let mut data = data(rust_tag(), json!([project("ash", &["rust"])]));
+        data.newsletter_editions = serde_json::from_value(json!([
+            {"date": "2019-06-12", "title": "Newsletter 002", "url": "http://eepurl.com/gJh88j"},
+            {"date": "12/06/2019", "title": "Newsletter 001", "url": "http://eepurl.com/gI3v89"},
+        ]))
+        .expect("lenient dates");
+        data.sponsorships = serde_json::from_value(json!([{
+            "recipient": "rust-analyzer",
+            "platform": "opencollective",
+            "url": "https://opencollective.com/rust-analyzer",
+            "category": "Rust ecosystem",
+        }]))
+        .expect("optional start date");
+        assert_eq!(
+            validate(&data),
+            vec![
+                Issue {
+                    subject: Subject::NewsletterEdition(1),
+                    field: Field::Date,
+                    kind: IssueKind::MissingDate,
+                },
+                Issue {
+                    subject: Subject::Sponsorship {
+                        index: 0,
+                        recipient: "rust-analyzer".to_owned(),
+                    },
+                    field: Field::StartDate,
+                    kind: IssueKind::MissingDate,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn site_urls() {
+        assert!(is_valid_site_url(
+            "https://opensource-website-seed-basic.netlify.app/"
+        ));
+        assert!(is_valid_site_url("http://localhost:8000"));
+        assert!(!is_valid_site_url(""));
+        assert!(!is_valid_site_url("embark.dev"));
+        assert!(!is_valid_site_url("https://"));
+        assert!(!is_valid_site_url("https://embark.dev/open-source"));
+        assert!(!is_valid_site_url("https://embark.dev?a=b"));
+        assert!(!is_valid_site_url("https://embark .dev"));
+    }
+
+    #[test]
+    fn image_urls() {
+        assert!(is_valid_image_url("https://embark.dev/img/code.jpg"));
+        assert!(is_valid_image_url("/public/img/logo.png"));
+        assert!(!is_valid_image_url("//evil.com/img.png"));
+        assert!(!is_valid_image_url("img/logo.png"));
+        assert!(!is_valid_image_url("/img/a.png) , url(/b.png"));
+        assert!(!is_valid_image_url("data:image/png;base64,AAAA"));
+    }
+
+    #[test]
+    fn colors() {
+        assert!(is_valid_color("#731b00"));
+        assert!(is_valid_color("#fff"));
+        assert!(!is_valid_color("731b00"));
+        assert!(!is_valid_color("#12345"));
+        assert!(!is_valid_color("#gggggg"));
+    }
+}